@@ -1,5 +1,15 @@
+//! A [`poem_openapi`] counterpart of the axum endpoints in [`crate::auth`] and [`crate::metadata`],
+//! built on the parsing core in [`crate::query`].
+//!
+//! Served by its own `poem::Server`, alongside (not instead of) the axum `Router`, since nothing
+//! yet picks one implementation over the other; see `main.rs`.
+
+use crate::metadata::{Issuer, Metadata};
 use crate::query::AuthorizationRequestQuery;
-use poem_openapi::{payload::PlainText, OpenApi};
+use poem_openapi::{
+    payload::{Json, PlainText},
+    OpenApi,
+};
 
 pub struct Api;
 
@@ -10,4 +20,15 @@ impl Api {
     async fn index(&self, _test: AuthorizationRequestQuery) -> PlainText<&'static str> {
         PlainText("Hello World")
     }
+
+    /// Authorization server metadata, as defined by
+    /// [RFC 8414](https://datatracker.ietf.org/doc/html/rfc8414). Served here in addition to the
+    /// axum implementation at [`crate::metadata::metadata`], since nothing yet picks one
+    /// implementation over the other.
+    #[oai(path = "/.well-known/oauth-authorization-server", method = "get")]
+    async fn metadata(&self) -> Json<Metadata> {
+        // TODO: Derive the issuer and scope registry from server configuration once one exists.
+        let issuer = Issuer::new(url::Url::parse("https://127.0.0.1:3001").unwrap()).unwrap();
+        Json(Metadata::new(&issuer, &crate::auth::AllowAllScopes))
+    }
 }
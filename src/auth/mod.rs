@@ -1,6 +1,13 @@
 mod query;
 
+use axum::response::Response;
+
 pub use query::AuthorizationRequestQuery;
+pub(crate) use query::{
+    AllowAllRedirectUris, AllowAllScopes, ClientRegistration, ClientRegistry, RedirectUri,
+    ScopeRegistry, StaticClientRegistry, StaticScopeRegistry, SUPPORTED_CODE_CHALLENGE_METHODS,
+    SUPPORTED_RESPONSE_TYPES,
+};
 
 #[utoipa::path(
     get,
@@ -10,12 +17,22 @@ pub use query::AuthorizationRequestQuery;
         (status = 303, description = "
 Location will be set to request_uri.
 
-If authorization is given, the `code`, the granted `scope` and the `state` parameter preserved as-is will be added to the Location's query string.
+If authorization is given, the `code`, the granted `scope` and the `state` parameter preserved as-is will be added to the Location's query string, or to its fragment if `response_mode=fragment` was requested or, with no `response_mode`, if `response_type` includes `token` or `id_token`.
 
-If an error occurs or authorization is not given, an `error` parameter with an explaination will be added to the query string"),
+If an error occurs or authorization is not given, an `error` parameter with an explaination will be added to the query string. `prompt=none` always fails this way with `error=login_required`, since this server never has an end-user session."),
+        (status = 200, description = "Returned instead of a 303 when `response_mode=form_post` was requested: a self-submitting HTML form POSTing the same parameters to redirect_uri."),
         (status = 400, description = "The request_uri is not valid or registered.")
     )
 )]
-pub async fn authorization(_test: AuthorizationRequestQuery) -> &'static str {
-    "Hello World"
+// TODO: Once this handler checks for an existing end-user session, a session that exists but
+// cannot satisfy the request without displaying UI must redirect with `error=interaction_required`
+// instead of falling through to the placeholder grant below, per OpenID Connect Core section
+// 3.1.2.6. `prompt=none` with no session at all is already rejected with `error=login_required`
+// while parsing the query, since that case never depends on what the handler does.
+// TODO: This always grants authorization without ever issuing a real `code`, `token` or
+// `id_token`, since there is no end-user session or consent step yet. It already renders through
+// `response_mode` (see `AuthorizationRequestQuery::render_success`) so that part of the response
+// won't need to change once a real grant decision replaces this placeholder.
+pub async fn authorization(query: AuthorizationRequestQuery) -> Response {
+    query.render_success(Vec::new())
 }
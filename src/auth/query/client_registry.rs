@@ -0,0 +1,66 @@
+use std::collections::{HashMap, HashSet};
+
+use url::Url;
+
+/// Decides which `redirect_uri`s are registered for a given client.
+///
+/// Threaded through [`RedirectUri::new`](super::redirect_uri::RedirectUri::new) so that a
+/// `redirect_uri` is only ever accepted when it exactly matches one previously registered for the
+/// `client_id` it was sent with, per [RFC 6749 section
+/// 3.1.2.3](https://datatracker.ietf.org/doc/html/rfc6749#section-3.1.2.3): accepting an
+/// unregistered `redirect_uri` would turn the authorization endpoint into an open redirect.
+pub trait ClientRegistry: Send + Sync {
+    /// Returns `true` if `redirect_uri` is registered for `client_id`.
+    fn is_registered_redirect_uri(&self, client_id: &str, redirect_uri: &Url) -> bool;
+}
+
+/// A [`ClientRegistry`] that accepts any `redirect_uri` for any client.
+///
+/// A placeholder until a real client store is wired into server state; see the `TODO` at the
+/// `from_query` call site.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct AllowAllRedirectUris;
+
+impl ClientRegistry for AllowAllRedirectUris {
+    fn is_registered_redirect_uri(&self, _client_id: &str, _redirect_uri: &Url) -> bool {
+        true
+    }
+}
+
+/// A single client's registration: the set of `redirect_uri`s it may use.
+#[derive(Debug, Clone, Default)]
+pub struct ClientRegistration {
+    redirect_uris: HashSet<Url>,
+}
+
+impl ClientRegistration {
+    pub fn new(redirect_uris: impl IntoIterator<Item = Url>) -> Self {
+        Self {
+            redirect_uris: redirect_uris.into_iter().collect(),
+        }
+    }
+}
+
+/// A [`ClientRegistry`] backed by a fixed, in-memory map of `client_id` to its
+/// [`ClientRegistration`].
+///
+/// Matches `redirect_uri` by exact equality only, per [RFC 6749 section
+/// 3.1.2.3](https://datatracker.ietf.org/doc/html/rfc6749#section-3.1.2.3): no substring or
+/// prefix matching, and scheme and port are significant, since `Url`'s `Eq` impl already compares
+/// every component.
+#[derive(Debug, Clone, Default)]
+pub struct StaticClientRegistry(HashMap<String, ClientRegistration>);
+
+impl StaticClientRegistry {
+    pub fn new(clients: impl IntoIterator<Item = (String, ClientRegistration)>) -> Self {
+        Self(clients.into_iter().collect())
+    }
+}
+
+impl ClientRegistry for StaticClientRegistry {
+    fn is_registered_redirect_uri(&self, client_id: &str, redirect_uri: &Url) -> bool {
+        self.0
+            .get(client_id)
+            .is_some_and(|registration| registration.redirect_uris.contains(redirect_uri))
+    }
+}
@@ -0,0 +1,64 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
+
+use super::redirect_uri::RedirectUri;
+use super::AuthorizationQueryParsingError as Error;
+use super::CodeChallengeMethod;
+
+/// A PKCE code challenge as defined in
+/// [section 4.2](https://datatracker.ietf.org/doc/html/rfc7636#section-4.2) of the RFC.
+///
+/// Must be 43-128 characters long and use only the unreserved characters of
+/// [section 2.3](https://datatracker.ietf.org/doc/html/rfc3986#section-2.3) of RFC 3986.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CodeChallenge(String);
+
+/// Whether `code_verifier` is syntactically valid per
+/// [section 4.1](https://datatracker.ietf.org/doc/html/rfc7636#section-4.1) of the RFC: the same
+/// 43-128 character unreserved-set grammar `code_challenge` itself is held to.
+fn is_valid_verifier(code_verifier: &str) -> bool {
+    let length_is_valid = (43..=128).contains(&code_verifier.len());
+    let all_valid = code_verifier
+        .bytes()
+        .all(|byte| byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~'));
+
+    length_is_valid && all_valid
+}
+
+impl CodeChallenge {
+    pub fn new(code_challenge: &str, redirect_uri: &RedirectUri) -> Result<Self, Error> {
+        if !is_valid_verifier(code_challenge) {
+            return Err(Error::InvalidCodeChallenge(
+                code_challenge.to_owned(),
+                redirect_uri.clone(),
+            ));
+        }
+
+        Ok(Self(code_challenge.to_owned()))
+    }
+
+    pub fn get(&self) -> &str {
+        &self.0
+    }
+
+    /// Verifies the `code_verifier` presented at the token endpoint against this challenge, as
+    /// defined in [section 4.6](https://datatracker.ietf.org/doc/html/rfc7636#section-4.6) of the
+    /// RFC: for `S256`, `code_verifier` is hashed and compared in constant time; for `plain`, the
+    /// RFC defines the transformation as the identity, so the verifier and the stored challenge
+    /// are compared directly.
+    pub fn verify(&self, code_verifier: &str, method: CodeChallengeMethod) -> bool {
+        if !is_valid_verifier(code_verifier) {
+            return false;
+        }
+
+        match method {
+            CodeChallengeMethod::Plain => code_verifier == self.0,
+            CodeChallengeMethod::S256 => {
+                let digest = Sha256::digest(code_verifier.as_bytes());
+                let encoded = URL_SAFE_NO_PAD.encode(digest);
+                encoded.as_bytes().ct_eq(self.0.as_bytes()).into()
+            }
+        }
+    }
+}
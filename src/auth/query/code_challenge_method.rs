@@ -0,0 +1,29 @@
+use super::redirect_uri::RedirectUri;
+use super::AuthorizationQueryParsingError as Error;
+
+/// The transformation applied to the `code_verifier` to produce the `code_challenge`, as defined
+/// in [section 4.2](https://datatracker.ietf.org/doc/html/rfc7636#section-4.2) of the RFC.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum CodeChallengeMethod {
+    Plain,
+    S256,
+}
+
+impl CodeChallengeMethod {
+    /// Defaults to [`Self::Plain`] per the RFC when a `code_challenge` is present but no method
+    /// was given.
+    pub fn default_for_challenge() -> Self {
+        Self::Plain
+    }
+
+    pub fn new(code_challenge_method: &str, redirect_uri: &RedirectUri) -> Result<Self, Error> {
+        match code_challenge_method {
+            "plain" => Ok(Self::Plain),
+            "S256" => Ok(Self::S256),
+            _ => Err(Error::InvalidCodeChallengeMethod(
+                code_challenge_method.to_owned(),
+                redirect_uri.clone(),
+            )),
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use super::redirect_uri::RedirectUri;
+use super::AuthorizationQueryParsingError as Error;
+
+/// The `display` parameter, as defined by [OpenID Connect Core section
+/// 3.1.2.1](https://openid.net/specs/openid-connect-core-1_0.html#AuthRequest): how the
+/// authentication and consent UI should be displayed to the end user.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Display {
+    /// Full User Agent page view. The default when `display` is not sent.
+    Page,
+    /// Popup User Agent window.
+    Popup,
+    /// Device that leverages a touch interface.
+    Touch,
+    /// Feature phone.
+    Wap,
+}
+
+impl Display {
+    pub fn new(display: &str, redirect_uri: &RedirectUri) -> Result<Self, Error> {
+        match display {
+            "page" => Ok(Self::Page),
+            "popup" => Ok(Self::Popup),
+            "touch" => Ok(Self::Touch),
+            "wap" => Ok(Self::Wap),
+            _ => Err(Error::InvalidDisplay(display.to_owned(), redirect_uri.clone())),
+        }
+    }
+}
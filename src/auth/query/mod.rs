@@ -10,31 +10,50 @@ use url::Url;
 #[cfg(test)]
 mod test;
 
+mod client_registry;
+mod code_challenge;
+mod code_challenge_method;
+mod display;
 mod opaque_parameters;
+mod prompt;
 mod redirect_uri;
+mod request_object;
+mod response_mode;
 mod response_type;
 mod scope;
+mod scope_registry;
 
-use self::{opaque_parameters::OpaqueParameters, redirect_uri::RedirectUri};
+use self::opaque_parameters::OpaqueParameters;
 use self::{response_type::ResponseType, scope::ScopeList};
+use self::{code_challenge::CodeChallenge, code_challenge_method::CodeChallengeMethod};
+use self::display::Display;
+use self::prompt::PromptSet;
+use self::response_mode::ResponseMode;
+pub use self::client_registry::{
+    AllowAllRedirectUris, ClientRegistration, ClientRegistry, StaticClientRegistry,
+};
+pub use self::scope_registry::{AllowAllScopes, ScopeRegistry, StaticScopeRegistry};
+pub(crate) use self::redirect_uri::RedirectUri;
 
 #[derive(Debug, PartialEq)]
 enum AuthorizationQueryParams {
     ResponseType,
-    ClientId,
     RedirectUri,
     Scope,
     State,
+    CodeChallenge,
+    CodeChallengeMethod,
 }
 
 impl AuthorizationQueryParams {
     fn name(&self) -> &'static str {
         match self {
             Self::ResponseType => "response_type",
-            Self::ClientId => "client_id",
             Self::RedirectUri => "redirect_uri",
             Self::Scope => "scope",
             Self::State => "state",
+            Self::CodeChallenge => "code_challenge",
+            Self::CodeChallengeMethod => "code_challenge_method",
         }
     }
 }
@@ -42,6 +61,22 @@ impl AuthorizationQueryParams {
 pub type ClientId = String;
 pub type State = String;
 
+/// Response types this server accepts, kept in sync with [`ResponseType::new`] so the RFC 8414
+/// metadata document never drifts from what the parser actually accepts. Every non-empty
+/// combination of `code`, `token` and `id_token` is supported.
+pub(crate) const SUPPORTED_RESPONSE_TYPES: &[&str] = &[
+    "code",
+    "token",
+    "id_token",
+    "code id_token",
+    "code token",
+    "id_token token",
+    "code id_token token",
+];
+
+/// PKCE code challenge methods this server accepts, kept in sync with [`CodeChallengeMethod::new`].
+pub(crate) const SUPPORTED_CODE_CHALLENGE_METHODS: &[&str] = &["plain", "S256"];
+
 /// Represents the authorization request query.
 #[derive(Debug, PartialEq, Eq)]
 pub struct AuthorizationRequestQuery {
@@ -52,6 +87,23 @@ pub struct AuthorizationRequestQuery {
     redirect_uri: RedirectUri,
     scope: ScopeList,
     state: Option<State>,
+    /// PKCE code challenge, see [RFC 7636](https://datatracker.ietf.org/doc/html/rfc7636).
+    code_challenge: Option<CodeChallenge>,
+    /// Defaults to [`CodeChallengeMethod::Plain`] when [`Self::code_challenge`] is set but no
+    /// method was given. `None` when no `code_challenge` was sent at all.
+    code_challenge_method: Option<CodeChallengeMethod>,
+    /// Defaults to [`ResponseMode::default_for_response_type`] when omitted.
+    response_mode: ResponseMode,
+    /// Opaque value used to associate a client session with an ID Token, as defined by [OpenID
+    /// Connect Core section 3.1.2.1](https://openid.net/specs/openid-connect-core-1_0.html#AuthRequest).
+    /// Preserved as-is through code issuance so it can later be embedded in the signed ID token.
+    nonce: Option<String>,
+    /// Whether, and how, the end user should be re-prompted for authentication or consent.
+    prompt: Option<PromptSet>,
+    /// Maximum elapsed time in seconds since the end user was last actively authenticated.
+    max_age: Option<u64>,
+    /// How the authentication and consent UI should be displayed.
+    display: Option<Display>,
 }
 
 #[derive(Debug, thiserror::Error, PartialEq)]
@@ -71,6 +123,38 @@ pub enum AuthorizationQueryParsingError {
     InvalidScope(String, RedirectUri),
     #[error("the redirect_uri is invalid")]
     InvalidUri,
+    #[error("the redirect_uri is invalid: {0}")]
+    InvalidRedirectUri(redirect_uri::RedirectUriRejectionReason),
+    #[error("invalid code_challenge `{0:?}`. check RFC 7636 section 4.2 for the allowed format")]
+    InvalidCodeChallenge(String, RedirectUri),
+    #[error("invalid code_challenge_method `{0:?}`. only \"plain\" and \"S256\" are supported")]
+    InvalidCodeChallengeMethod(String, RedirectUri),
+    #[error("code_challenge_method was sent without a code_challenge")]
+    CodeChallengeMethodWithoutChallenge(RedirectUri),
+    #[error("\"request\" and \"request_uri\" are mutually exclusive")]
+    RequestObjectConflict(RedirectUri),
+    #[error("the request object is not a valid JWT")]
+    InvalidRequestObject(RedirectUri),
+    #[error("the request_uri could not be fetched")]
+    UnreachableRequestUri(RedirectUri),
+    #[error("the client_id claim of the request object does not match the client_id parameter")]
+    ClientIdMismatch(RedirectUri),
+    #[error("the redirect_uri claim of the request object is invalid or not registered")]
+    InvalidRequestObjectRedirectUri(RedirectUri),
+    #[error("unsupported response_mode. only \"query\", \"fragment\" and \"form_post\" are supported")]
+    UnsupportedResponseMode(RedirectUri),
+    #[error("scope `{0:?}` is not known to the scope registry")]
+    UnknownScope(String, RedirectUri),
+    #[error("invalid prompt `{0:?}`. only \"none\", \"login\", \"consent\" and \"select_account\" are supported")]
+    InvalidPrompt(String, RedirectUri),
+    #[error("\"none\" cannot be combined with any other prompt value")]
+    PromptNoneCombined(RedirectUri),
+    #[error("invalid max_age `{0:?}`. it must be a non-negative integer")]
+    InvalidMaxAge(String, RedirectUri),
+    #[error("invalid display `{0:?}`. only \"page\", \"popup\", \"touch\" and \"wap\" are supported")]
+    InvalidDisplay(String, RedirectUri),
+    #[error("prompt=none was requested but this server never has an end-user session")]
+    LoginRequired(RedirectUri),
 }
 
 impl AuthorizationQueryParsingError {
@@ -80,8 +164,22 @@ impl AuthorizationQueryParsingError {
             Self::MissingParameter(_, _) | Self::RepeatedParameter(_) | Self::ParsingError(_) => {
                 "invalid_request"
             }
-            Self::InvalidScope(_, _) => "invalid_scope",
-            Self::InvalidUri => "server_error",
+            Self::InvalidScope(_, _) | Self::UnknownScope(_, _) => "invalid_scope",
+            Self::InvalidUri | Self::InvalidRedirectUri(_) => "server_error",
+            Self::InvalidCodeChallenge(_, _)
+            | Self::InvalidCodeChallengeMethod(_, _)
+            | Self::CodeChallengeMethodWithoutChallenge(_) => "invalid_request",
+            Self::RequestObjectConflict(_)
+            | Self::InvalidRequestObject(_)
+            | Self::UnreachableRequestUri(_)
+            | Self::ClientIdMismatch(_)
+            | Self::InvalidRequestObjectRedirectUri(_) => "invalid_request",
+            Self::UnsupportedResponseMode(_) => "invalid_request",
+            Self::InvalidPrompt(_, _)
+            | Self::PromptNoneCombined(_)
+            | Self::InvalidMaxAge(_, _)
+            | Self::InvalidDisplay(_, _) => "invalid_request",
+            Self::LoginRequired(_) => "login_required",
         }
     }
 }
@@ -90,59 +188,86 @@ impl IntoResponse for AuthorizationQueryParsingError {
     fn into_response(self) -> Response {
         let standard_error_text = self.standard_error_text();
         let error_text = self.to_string();
-        match self {
-            Self::InvalidUri => (StatusCode::BAD_REQUEST, error_text).into_response(),
+        let redirect_uri = match &self {
+            Self::InvalidUri | Self::InvalidRedirectUri(_) => None,
             Self::UnsupportedResponseType(redirect_uri)
             | Self::InvalidScope(_, redirect_uri)
             | Self::MissingParameter(_, redirect_uri)
             | Self::ParsingError(redirect_uri)
-            | Self::RepeatedParameter(redirect_uri) => {
-                let mut query: Vec<(String, String)> = match serde_urlencoded::from_str(
-                    redirect_uri.get().query().unwrap_or_default(),
-                ) {
-                    Ok(vec) => vec,
-                    Err(_) => return (StatusCode::BAD_REQUEST, error_text).into_response(),
-                };
-                query.push(("error".to_owned(), standard_error_text.to_owned()));
-
-                let query = match serde_urlencoded::to_string(query) {
-                    Ok(str) => str,
-                    Err(_) => return (StatusCode::BAD_REQUEST, error_text).into_response(),
-                };
+            | Self::InvalidCodeChallenge(_, redirect_uri)
+            | Self::InvalidCodeChallengeMethod(_, redirect_uri)
+            | Self::CodeChallengeMethodWithoutChallenge(redirect_uri)
+            | Self::RequestObjectConflict(redirect_uri)
+            | Self::InvalidRequestObject(redirect_uri)
+            | Self::UnreachableRequestUri(redirect_uri)
+            | Self::ClientIdMismatch(redirect_uri)
+            | Self::InvalidRequestObjectRedirectUri(redirect_uri)
+            | Self::UnsupportedResponseMode(redirect_uri)
+            | Self::UnknownScope(_, redirect_uri)
+            | Self::InvalidPrompt(_, redirect_uri)
+            | Self::PromptNoneCombined(redirect_uri)
+            | Self::InvalidMaxAge(_, redirect_uri)
+            | Self::InvalidDisplay(_, redirect_uri)
+            | Self::RepeatedParameter(redirect_uri)
+            | Self::LoginRequired(redirect_uri) => Some(redirect_uri.get()),
+        };
 
-                let mut redirect_uri = redirect_uri.get().clone();
-                redirect_uri.set_query(Some(&query));
+        let crate::oauth_error::AuthorizationError { kind, body } =
+            crate::oauth_error::render_error_response(redirect_uri, standard_error_text, error_text);
 
-                (
-                    StatusCode::SEE_OTHER,
-                    [("Location", redirect_uri.to_string())],
-                    error_text,
-                )
-                    .into_response()
+        match kind {
+            crate::oauth_error::AuthorizationErrorKind::BadRequest => {
+                (StatusCode::BAD_REQUEST, body).into_response()
+            }
+            crate::oauth_error::AuthorizationErrorKind::Redirect { location } => {
+                (StatusCode::SEE_OTHER, [("Location", location)], body).into_response()
             }
         }
     }
 }
 
-impl std::str::FromStr for AuthorizationRequestQuery {
-    type Err = AuthorizationQueryParsingError;
-    /// Tries to generate itself from a still percentage-encoded string.
-    fn from_str(query: &str) -> Result<Self, Self::Err> {
+/// Implementations for parsing
+impl AuthorizationRequestQuery {
+    /// Tries to generate itself from a still percent-encoded string.
+    ///
+    /// Async because resolving a `request_uri` Request Object (see
+    /// [RFC 9101](https://datatracker.ietf.org/doc/html/rfc9101)) requires an HTTP fetch.
+    ///
+    /// `scope_registry` decides which scopes may be requested; see [`ScopeRegistry`].
+    ///
+    /// `client_registry` decides which `redirect_uri`s are registered for the requesting client;
+    /// see [`ClientRegistry`]. `client_id` must be resolved before `redirect_uri` can be
+    /// validated against it, so it is parsed out of the query ahead of everything else.
+    async fn from_query(
+        query: &str,
+        scope_registry: &dyn ScopeRegistry,
+        client_registry: &dyn ClientRegistry,
+    ) -> Result<Self, AuthorizationQueryParsingError> {
         use AuthorizationQueryParams as Params;
         use AuthorizationQueryParsingError as Error;
 
         #[derive(Deserialize)]
-        struct RedirectUriDeserializer {
+        struct ClientIdAndRedirectUri {
+            client_id: String,
             redirect_uri: String,
         }
-        let redirect_uri = serde_urlencoded::from_str::<RedirectUriDeserializer>(query)
-            .map_err(|_| Error::InvalidUri)?
-            .redirect_uri;
+        let ClientIdAndRedirectUri {
+            client_id,
+            redirect_uri,
+        } = serde_urlencoded::from_str::<ClientIdAndRedirectUri>(query)
+            .map_err(|_| Error::InvalidUri)?;
+
+        if client_id.is_empty() {
+            return Err(Error::InvalidUri);
+        }
 
         let redirect_uri = Url::parse(&redirect_uri).map_err(|_| Error::InvalidUri)?;
-        let redirect_uri = RedirectUri::new(redirect_uri).map_err(|_| Error::InvalidUri)?;
+        let mut redirect_uri = RedirectUri::new(redirect_uri, &client_id, client_registry)?;
 
-        // All logic for rejecting URIs must go ABOVE HERE and must return InvalidUri.
+        // All logic for rejecting a client_id/redirect_uri pair must go ABOVE HERE and must
+        // return InvalidUri or InvalidRedirectUri, the two variants that never redirect: every
+        // other error variant below this point redirects to
+        // `redirect_uri`, so it must already be trustworthy.
 
         #[derive(Debug, Deserialize)]
         struct DeserializableSelf {
@@ -151,6 +276,15 @@ impl std::str::FromStr for AuthorizationRequestQuery {
             redirect_uri: Option<String>,
             scope: Option<String>,
             state: Option<String>,
+            code_challenge: Option<String>,
+            code_challenge_method: Option<String>,
+            request: Option<String>,
+            request_uri: Option<String>,
+            response_mode: Option<String>,
+            nonce: Option<String>,
+            prompt: Option<String>,
+            max_age: Option<String>,
+            display: Option<String>,
             #[serde(flatten)]
             opaque_parameters: OpaqueParameters,
         }
@@ -166,12 +300,22 @@ impl std::str::FromStr for AuthorizationRequestQuery {
                     redirect_uri: self.redirect_uri,
                     scope: empty_to_none(self.scope),
                     state: empty_to_none(self.state),
+                    code_challenge: empty_to_none(self.code_challenge),
+                    code_challenge_method: empty_to_none(self.code_challenge_method),
+                    request: empty_to_none(self.request),
+                    request_uri: empty_to_none(self.request_uri),
+                    response_mode: empty_to_none(self.response_mode),
+                    nonce: empty_to_none(self.nonce),
+                    prompt: empty_to_none(self.prompt),
+                    max_age: empty_to_none(self.max_age),
+                    display: empty_to_none(self.display),
                     opaque_parameters: self.opaque_parameters,
                 }
             }
         }
 
-        let deserializable_self = match serde_urlencoded::from_str::<DeserializableSelf>(query) {
+        let mut deserializable_self = match serde_urlencoded::from_str::<DeserializableSelf>(query)
+        {
             Ok(deserializable_self) => deserializable_self,
             Err(error) => {
                 let error = if error.to_string().starts_with("duplicate field") {
@@ -186,6 +330,54 @@ impl std::str::FromStr for AuthorizationRequestQuery {
 
         tracing::trace!("Finished parsing query: {:?}", deserializable_self);
 
+        // Request Objects (RFC 9101): fields carried inside a signed JWT take precedence over the
+        // plain query parameters they duplicate, but `client_id` must still be sent as a plain
+        // query parameter and must match the claim inside the object.
+        match (&deserializable_self.request, &deserializable_self.request_uri) {
+            (Some(_), Some(_)) => return Err(Error::RequestObjectConflict(redirect_uri)),
+            (None, None) => {}
+            (request, request_uri) => {
+                let jwt = match request {
+                    Some(request) => request.clone(),
+                    None => {
+                        request_object::fetch(request_uri.as_deref().unwrap(), &redirect_uri)
+                            .await?
+                    }
+                };
+
+                let claims = request_object::decode_claims(&jwt, &redirect_uri)?;
+
+                match &claims.client_id {
+                    Some(claim_client_id) if claim_client_id == &client_id => {}
+                    _ => return Err(Error::ClientIdMismatch(redirect_uri)),
+                }
+
+                // A `redirect_uri` claim takes precedence the same as every other field here, but
+                // it must be re-validated the same way the plain-query `redirect_uri` was above:
+                // an invalid or unregistered claim is rejected rather than silently kept, and
+                // every check from here on can keep assuming `redirect_uri` is trustworthy.
+                if let Some(claim_redirect_uri) = &claims.redirect_uri {
+                    let claim_redirect_uri = Url::parse(claim_redirect_uri)
+                        .ok()
+                        .and_then(|url| RedirectUri::new(url, &client_id, client_registry).ok())
+                        .ok_or_else(|| {
+                            Error::InvalidRequestObjectRedirectUri(redirect_uri.clone())
+                        })?;
+                    redirect_uri = claim_redirect_uri;
+                }
+
+                deserializable_self.response_type =
+                    claims.response_type.or(deserializable_self.response_type);
+                deserializable_self.scope = claims.scope.or(deserializable_self.scope);
+                deserializable_self.state = claims.state.or(deserializable_self.state);
+                deserializable_self.code_challenge =
+                    claims.code_challenge.or(deserializable_self.code_challenge);
+                deserializable_self.code_challenge_method = claims
+                    .code_challenge_method
+                    .or(deserializable_self.code_challenge_method);
+            }
+        }
+
         let response_type = ResponseType::new(&deserializable_self.response_type.ok_or(
             Error::MissingParameter(Params::ResponseType.name(), redirect_uri.clone()),
         )?)
@@ -193,16 +385,74 @@ impl std::str::FromStr for AuthorizationRequestQuery {
             Error::UnsupportedResponseType(redirect_uri.clone())
         })?;
 
-        let client_id = deserializable_self
-            .client_id
-            .ok_or(Error::MissingParameter(
-                Params::ClientId.name(),
-                redirect_uri.clone(),
-            ))?;
-
         let scope = ScopeList::try_from(&deserializable_self.scope.unwrap_or_default() as &str)
             .map_err(|invalid_scope| Error::InvalidScope(invalid_scope.0, redirect_uri.clone()))?;
 
+        for scope in &scope.0 {
+            if !scope_registry.is_known(scope.as_str()) {
+                return Err(Error::UnknownScope(
+                    scope.as_str().to_owned(),
+                    redirect_uri.clone(),
+                ));
+            }
+        }
+
+        if deserializable_self.code_challenge.is_none()
+            && deserializable_self.code_challenge_method.is_some()
+        {
+            return Err(Error::CodeChallengeMethodWithoutChallenge(
+                redirect_uri.clone(),
+            ));
+        }
+
+        let code_challenge = deserializable_self
+            .code_challenge
+            .map(|code_challenge| CodeChallenge::new(&code_challenge, &redirect_uri))
+            .transpose()?;
+
+        let code_challenge_method = deserializable_self
+            .code_challenge_method
+            .map(|code_challenge_method| {
+                CodeChallengeMethod::new(&code_challenge_method, &redirect_uri)
+            })
+            .transpose()?
+            .or(code_challenge
+                .is_some()
+                .then(CodeChallengeMethod::default_for_challenge));
+
+        let response_mode = deserializable_self
+            .response_mode
+            .map(|response_mode| ResponseMode::new(&response_mode, &redirect_uri))
+            .transpose()?
+            .unwrap_or_else(|| ResponseMode::default_for_response_type(&response_type));
+
+        let prompt = deserializable_self
+            .prompt
+            .map(|prompt| PromptSet::new(&prompt, &redirect_uri))
+            .transpose()?;
+
+        // `prompt=none` means "do not display any authentication or consent UI, fail with
+        // login_required/interaction_required instead if one would be needed". This server never
+        // has an end-user session to begin with, so it can never satisfy that without displaying
+        // UI: reject it here rather than silently granting authorization.
+        if prompt.as_ref().is_some_and(PromptSet::has_none) {
+            return Err(Error::LoginRequired(redirect_uri));
+        }
+
+        let max_age = deserializable_self
+            .max_age
+            .map(|max_age| {
+                max_age
+                    .parse::<u64>()
+                    .map_err(|_err| Error::InvalidMaxAge(max_age.clone(), redirect_uri.clone()))
+            })
+            .transpose()?;
+
+        let display = deserializable_self
+            .display
+            .map(|display| Display::new(&display, &redirect_uri))
+            .transpose()?;
+
         let result = AuthorizationRequestQuery {
             opaque_parameters: deserializable_self.opaque_parameters,
             response_type,
@@ -210,17 +460,21 @@ impl std::str::FromStr for AuthorizationRequestQuery {
             redirect_uri,
             scope,
             state: deserializable_self.state,
+            code_challenge,
+            code_challenge_method,
+            response_mode,
+            nonce: deserializable_self.nonce,
+            prompt,
+            max_age,
+            display,
         };
 
         tracing::trace!("Resulted in: {:?}", result);
 
         Ok(result)
     }
-}
 
-/// Implementations for parsing
-impl AuthorizationRequestQuery {
-    /// Simply maps to `Self::try_from_query`
+    /// Simply maps to `Self::from_query`
     #[instrument(name = "parse_authorization_query", skip_all)]
     async fn internal_from_request(req: Request) -> Result<Self, AuthorizationQueryParsingError> {
         // this string will be percent-encoded. we'll have to decode it!
@@ -228,12 +482,25 @@ impl AuthorizationRequestQuery {
 
         tracing::trace!("Started to parse query: {:?}", query);
 
-        let result = query.parse();
+        // TODO: Source these from app configuration once a ScopeRegistry and a real client
+        // store (ClientRegistry) are wired into server state.
+        let result = Self::from_query(query, &AllowAllScopes, &AllowAllRedirectUris).await;
 
         tracing::trace!("Resulted in: {:?}", result);
 
         result
     }
+
+    /// Renders a successful authorization response via [`ResponseMode::render_success`], honoring
+    /// whichever `response_mode` this request asked for. `state`, if the request carried one, is
+    /// always echoed back alongside `params`, per [RFC 6749 section
+    /// 4.1.2](https://datatracker.ietf.org/doc/html/rfc6749#section-4.1.2).
+    pub(crate) fn render_success(&self, mut params: Vec<(String, String)>) -> Response {
+        if let Some(state) = &self.state {
+            params.push(("state".to_owned(), state.clone()));
+        }
+        self.response_mode.render_success(&self.redirect_uri, &params)
+    }
 }
 
 #[async_trait::async_trait]
@@ -314,6 +581,97 @@ impl utoipa::IntoParams for AuthorizationRequestQuery {
                         .schema_type(SchemaType::String)
                 ))
                 .build(),
+            ParameterBuilder::new()
+                .name("code_challenge")
+                .required(Required::False)
+                .parameter_in(ParameterIn::Query)
+                .description(Some("PKCE code challenge, as defined by [RFC 7636](https://datatracker.ietf.org/doc/html/rfc7636#section-4.2). Required for public clients."))
+                .schema(Some(
+                    ObjectBuilder::new()
+                        .schema_type(SchemaType::String)
+                ))
+                .build(),
+            ParameterBuilder::new()
+                .name("code_challenge_method")
+                .required(Required::False)
+                .parameter_in(ParameterIn::Query)
+                .description(Some("Method used to derive `code_challenge` from the `code_verifier`. Either \"plain\" or \"S256\". Defaults to \"plain\" when `code_challenge` is sent without it."))
+                .schema(Some(
+                    ObjectBuilder::new()
+                        .schema_type(SchemaType::String)
+                ))
+                .build(),
+            ParameterBuilder::new()
+                .name("request")
+                .required(Required::False)
+                .parameter_in(ParameterIn::Query)
+                .description(Some("A signed JWT carrying the authorization request parameters, as defined by [RFC 9101](https://datatracker.ietf.org/doc/html/rfc9101). Mutually exclusive with `request_uri`."))
+                .schema(Some(
+                    ObjectBuilder::new()
+                        .schema_type(SchemaType::String)
+                ))
+                .build(),
+            ParameterBuilder::new()
+                .name("response_mode")
+                .required(Required::False)
+                .parameter_in(ParameterIn::Query)
+                .description(Some("How the response parameters are returned: \"query\", \"fragment\" or \"form_post\". Defaults to \"fragment\" when `response_type` includes \"token\" or \"id_token\", and to \"query\" otherwise."))
+                .schema(Some(
+                    ObjectBuilder::new()
+                        .schema_type(SchemaType::String)
+                ))
+                .build(),
+            ParameterBuilder::new()
+                .name("request_uri")
+                .required(Required::False)
+                .parameter_in(ParameterIn::Query)
+                .description(Some("An HTTPS URL from which the `request` JWT can be fetched, as defined by [RFC 9101](https://datatracker.ietf.org/doc/html/rfc9101). Mutually exclusive with `request`."))
+                .schema(Some(
+                    ObjectBuilder::new()
+                        .schema_type(SchemaType::String)
+                        .format(Some(SchemaFormat::KnownFormat(KnownFormat::Uri))))
+                )
+                .build(),
+            ParameterBuilder::new()
+                .name("nonce")
+                .required(Required::False)
+                .parameter_in(ParameterIn::Query)
+                .description(Some("Any string. Preserved through code issuance and embedded in the eventual ID token, as defined by [OpenID Connect Core](https://openid.net/specs/openid-connect-core-1_0.html#AuthRequest)."))
+                .schema(Some(
+                    ObjectBuilder::new()
+                        .schema_type(SchemaType::String)
+                ))
+                .build(),
+            ParameterBuilder::new()
+                .name("prompt")
+                .required(Required::False)
+                .parameter_in(ParameterIn::Query)
+                .description(Some("A space-separated list of one or more of \"none\", \"login\", \"consent\" and \"select_account\". \"none\" cannot be combined with any other value."))
+                .schema(Some(
+                    ObjectBuilder::new()
+                        .schema_type(SchemaType::String)
+                ))
+                .build(),
+            ParameterBuilder::new()
+                .name("max_age")
+                .required(Required::False)
+                .parameter_in(ParameterIn::Query)
+                .description(Some("Maximum elapsed time in seconds since the end user was last actively authenticated. Must be a non-negative integer."))
+                .schema(Some(
+                    ObjectBuilder::new()
+                        .schema_type(SchemaType::String)
+                ))
+                .build(),
+            ParameterBuilder::new()
+                .name("display")
+                .required(Required::False)
+                .parameter_in(ParameterIn::Query)
+                .description(Some("How the authentication and consent UI should be displayed: \"page\", \"popup\", \"touch\" or \"wap\". Defaults to \"page\"."))
+                .schema(Some(
+                    ObjectBuilder::new()
+                        .schema_type(SchemaType::String)
+                ))
+                .build(),
         ]
     }
 }
@@ -0,0 +1 @@
+pub use crate::opaque_parameters::OpaqueParameters;
@@ -0,0 +1,68 @@
+use super::redirect_uri::RedirectUri;
+use super::AuthorizationQueryParsingError as Error;
+
+/// The `prompt` parameter, as defined by [OpenID Connect Core section
+/// 3.1.2.1](https://openid.net/specs/openid-connect-core-1_0.html#AuthRequest): a space-delimited
+/// set of one or more of `none`, `login`, `consent` and `select_account`.
+///
+/// Tracks which values were present rather than their order, the same way
+/// [`ResponseType`](super::response_type::ResponseType) does. `none` cannot be combined with any
+/// other value, since it means "do not display any authentication or consent UI at all" while the
+/// others each request one.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct PromptSet {
+    none: bool,
+    login: bool,
+    consent: bool,
+    select_account: bool,
+}
+
+impl PromptSet {
+    /// Splits on spaces the same way [`ResponseType::new`](super::response_type::ResponseType::new)
+    /// does, rejecting duplicate or unknown values as well as `none` combined with anything else.
+    pub fn new(prompt: &str, redirect_uri: &RedirectUri) -> Result<Self, Error> {
+        let mut result = PromptSet::default();
+
+        for value in prompt.split(' ') {
+            match value {
+                "none" if !result.none => result.none = true,
+                "login" if !result.login => result.login = true,
+                "consent" if !result.consent => result.consent = true,
+                "select_account" if !result.select_account => result.select_account = true,
+                _ => return Err(Error::InvalidPrompt(prompt.to_owned(), redirect_uri.clone())),
+            }
+        }
+
+        if !(result.none || result.login || result.consent || result.select_account) {
+            return Err(Error::InvalidPrompt(prompt.to_owned(), redirect_uri.clone()));
+        }
+
+        if result.none && (result.login || result.consent || result.select_account) {
+            return Err(Error::PromptNoneCombined(redirect_uri.clone()));
+        }
+
+        Ok(result)
+    }
+
+    /// Whether `none` was present, i.e. whether the client requires that no authentication or
+    /// consent UI be displayed at all.
+    pub fn has_none(&self) -> bool {
+        self.none
+    }
+
+    /// Whether `login` was present, i.e. whether the end user must be re-authenticated.
+    pub fn has_login(&self) -> bool {
+        self.login
+    }
+
+    /// Whether `consent` was present, i.e. whether the end user must be re-prompted for consent.
+    pub fn has_consent(&self) -> bool {
+        self.consent
+    }
+
+    /// Whether `select_account` was present, i.e. whether the end user must be prompted to select
+    /// an account.
+    pub fn has_select_account(&self) -> bool {
+        self.select_account
+    }
+}
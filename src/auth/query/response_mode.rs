@@ -0,0 +1,119 @@
+use axum::response::{Html, IntoResponse, Redirect, Response};
+
+use super::redirect_uri::RedirectUri;
+use super::response_type::ResponseType;
+use super::AuthorizationQueryParsingError as Error;
+
+/// How the authorization response parameters are returned to the client, as defined by
+/// [OAuth 2.0 Multiple Response Type Encoding Practices](https://openid.net/specs/oauth-v2-multiple-response-types-1_0.html#ResponseModes).
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ResponseMode {
+    /// Parameters are appended to the redirect_uri's query string.
+    Query,
+    /// Parameters are appended to the redirect_uri's fragment.
+    Fragment,
+    /// Parameters are returned as a self-submitting HTML form that POSTs to the redirect_uri.
+    FormPost,
+}
+
+impl ResponseMode {
+    /// `code`-only responses default to `query`; once `response_type` carries `token` or
+    /// `id_token`, [OpenID Connect Core section
+    /// 3.2.2.6](https://openid.net/specs/openid-connect-core-1_0.html#ImplicitAuthorizationResponse)
+    /// mandates `fragment`, since those values must never be sent as a full-page-reload query
+    /// string where they could be logged by intermediaries.
+    pub fn default_for_response_type(response_type: &ResponseType) -> Self {
+        if response_type.has_token() || response_type.has_id_token() {
+            Self::Fragment
+        } else {
+            Self::Query
+        }
+    }
+
+    pub fn new(response_mode: &str, redirect_uri: &RedirectUri) -> Result<Self, Error> {
+        match response_mode {
+            "query" => Ok(Self::Query),
+            "fragment" => Ok(Self::Fragment),
+            "form_post" => Ok(Self::FormPost),
+            _ => Err(Error::UnsupportedResponseMode(redirect_uri.clone())),
+        }
+    }
+
+    /// Carries `params` back to `redirect_uri` the way this mode dictates: appended to the query
+    /// string for [`Self::Query`], appended after a `#` for [`Self::Fragment`], or as a
+    /// self-submitting HTML form that POSTs them to `redirect_uri` for [`Self::FormPost`].
+    pub fn render_success(self, redirect_uri: &RedirectUri, params: &[(String, String)]) -> Response {
+        match self.render_success_body(redirect_uri, params) {
+            RenderedSuccess::Redirect(location) => Redirect::to(&location).into_response(),
+            RenderedSuccess::Html(body) => Html(body).into_response(),
+        }
+    }
+
+    /// The framework-independent half of [`Self::render_success`], kept separate so the string it
+    /// builds can be asserted on directly instead of through an `axum::response::Response`.
+    pub(super) fn render_success_body(
+        self,
+        redirect_uri: &RedirectUri,
+        params: &[(String, String)],
+    ) -> RenderedSuccess {
+        let encoded =
+            serde_urlencoded::to_string(params).expect("param keys/values are plain strings");
+
+        match self {
+            Self::Query => {
+                let mut uri = redirect_uri.get().clone();
+                let query = match uri.query() {
+                    Some(existing) => format!("{existing}&{encoded}"),
+                    None => encoded,
+                };
+                uri.set_query(Some(&query));
+                RenderedSuccess::Redirect(uri.to_string())
+            }
+            Self::Fragment => {
+                let mut uri = redirect_uri.get().clone();
+                uri.set_fragment(Some(&encoded));
+                RenderedSuccess::Redirect(uri.to_string())
+            }
+            Self::FormPost => {
+                let inputs: String = params
+                    .iter()
+                    .map(|(name, value)| {
+                        format!(
+                            r#"<input type="hidden" name="{}" value="{}">"#,
+                            escape_html(name),
+                            escape_html(value)
+                        )
+                    })
+                    .collect();
+
+                RenderedSuccess::Html(format!(
+                    "<!DOCTYPE html><html><head><title>Submit This Form</title></head>\
+                     <body onload=\"document.forms[0].submit()\">\
+                     <form method=\"post\" action=\"{}\">{inputs}</form></body></html>",
+                    escape_html(redirect_uri.get().as_str())
+                ))
+            }
+        }
+    }
+}
+
+/// The body of a successful authorization response, before it's wrapped in an
+/// `axum::response::Response`.
+#[derive(Debug, PartialEq, Eq)]
+pub(super) enum RenderedSuccess {
+    /// The `Location` a `303` redirect should carry, for [`ResponseMode::Query`] and
+    /// [`ResponseMode::Fragment`].
+    Redirect(String),
+    /// The self-submitting HTML form body, for [`ResponseMode::FormPost`].
+    Html(String),
+}
+
+/// Escapes the characters that would let a `code`/`state`/`scope` value break out of an HTML
+/// attribute, since [`ResponseMode::render_success`]'s `form_post` document embeds them verbatim.
+fn escape_html(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
@@ -0,0 +1,52 @@
+/// The `response_type` parameter, as generalized by [OAuth 2.0 Multiple Response Type Encoding
+/// Practices](https://openid.net/specs/oauth-v2-multiple-response-types-1_0.html) to a
+/// space-delimited set of one or more of `code`, `token` and `id_token`.
+///
+/// Tracks which values were present rather than their order, so `"token code"` and `"code token"`
+/// parse to the same value.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct ResponseType {
+    code: bool,
+    id_token: bool,
+    token: bool,
+}
+
+impl ResponseType {
+    /// Splits on spaces the same way [`ScopeList`](super::scope::ScopeList) does, rejecting
+    /// duplicate or unknown values. Every non-empty combination of `code`, `token` and `id_token`
+    /// is accepted, since OAuth 2.0 / OpenID Connect define all of them.
+    pub fn new(response_type: &str) -> Result<Self, ()> {
+        let mut result = ResponseType::default();
+
+        for value in response_type.split(' ') {
+            match value {
+                "code" if !result.code => result.code = true,
+                "id_token" if !result.id_token => result.id_token = true,
+                "token" if !result.token => result.token = true,
+                _ => return Err(()),
+            }
+        }
+
+        if !(result.code || result.id_token || result.token) {
+            return Err(());
+        }
+
+        Ok(result)
+    }
+
+    /// Whether the `code` value was present, i.e. whether an authorization code should be issued.
+    pub fn has_code(&self) -> bool {
+        self.code
+    }
+
+    /// Whether the `id_token` value was present, i.e. whether an ID Token should be issued.
+    pub fn has_id_token(&self) -> bool {
+        self.id_token
+    }
+
+    /// Whether the `token` value was present, i.e. whether an access token should be issued
+    /// directly from the authorization endpoint.
+    pub fn has_token(&self) -> bool {
+        self.token
+    }
+}
@@ -0,0 +1 @@
+pub use crate::scope::{InvalidScope, Scope, ScopeList};
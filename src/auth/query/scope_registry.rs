@@ -0,0 +1,54 @@
+use std::collections::HashSet;
+
+/// Defines the universe of scopes an authorization server (or a specific client) is willing to
+/// grant. Injected into the parser so the set of valid scopes isn't hard-coded, and shared with
+/// the RFC 8414 metadata document so `scopes_supported` never drifts from what's actually
+/// accepted.
+pub trait ScopeRegistry: Send + Sync {
+    /// Returns `true` if `scope` may be requested.
+    fn is_known(&self, scope: &str) -> bool;
+
+    /// All scopes this registry knows about, used to populate the metadata document's
+    /// `scopes_supported` field. May be empty if the registry can't enumerate its universe (see
+    /// [`AllowAllScopes`]).
+    fn known_scopes(&self) -> Vec<String>;
+}
+
+/// A permissive placeholder [`ScopeRegistry`] that accepts any well-formed scope.
+///
+/// Used until the server has real configuration (or per-client allow-lists) to build a
+/// [`StaticScopeRegistry`] from.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct AllowAllScopes;
+
+impl ScopeRegistry for AllowAllScopes {
+    fn is_known(&self, _scope: &str) -> bool {
+        true
+    }
+
+    fn known_scopes(&self) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+/// A [`ScopeRegistry`] backed by a fixed, in-memory set of scopes.
+#[derive(Debug, Clone)]
+pub struct StaticScopeRegistry(HashSet<String>);
+
+impl StaticScopeRegistry {
+    pub fn new(scopes: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self(scopes.into_iter().map(Into::into).collect())
+    }
+}
+
+impl ScopeRegistry for StaticScopeRegistry {
+    fn is_known(&self, scope: &str) -> bool {
+        self.0.contains(scope)
+    }
+
+    fn known_scopes(&self) -> Vec<String> {
+        let mut scopes: Vec<String> = self.0.iter().cloned().collect();
+        scopes.sort();
+        scopes
+    }
+}
@@ -1,11 +1,31 @@
 use super::{
-    opaque_parameters::OpaqueParameters, redirect_uri::RedirectUri, response_type::ResponseType,
+    client_registry::{AllowAllRedirectUris, ClientRegistration, StaticClientRegistry},
+    code_challenge::CodeChallenge,
+    code_challenge_method::CodeChallengeMethod, display::Display, opaque_parameters::OpaqueParameters,
+    prompt::PromptSet,
+    redirect_uri::{RedirectUri, RedirectUriRejectionReason},
+    response_mode::{RenderedSuccess, ResponseMode}, response_type::ResponseType,
+    scope_registry::{AllowAllScopes, StaticScopeRegistry},
     AuthorizationQueryParams as Params, AuthorizationQueryParsingError as Error,
-    AuthorizationRequestQuery, ScopeList,
+    AuthorizationRequestQuery, ClientRegistry, ScopeList, ScopeRegistry,
 };
 
-fn parse_authorization_query(query: &str) -> Result<AuthorizationRequestQuery, Error> {
-    query.parse()
+async fn parse_authorization_query(query: &str) -> Result<AuthorizationRequestQuery, Error> {
+    AuthorizationRequestQuery::from_query(query, &AllowAllScopes, &AllowAllRedirectUris).await
+}
+
+async fn parse_authorization_query_with_registry(
+    query: &str,
+    scope_registry: &dyn ScopeRegistry,
+) -> Result<AuthorizationRequestQuery, Error> {
+    AuthorizationRequestQuery::from_query(query, scope_registry, &AllowAllRedirectUris).await
+}
+
+async fn parse_authorization_query_with_client_registry(
+    query: &str,
+    client_registry: &dyn ClientRegistry,
+) -> Result<AuthorizationRequestQuery, Error> {
+    AuthorizationRequestQuery::from_query(query, &AllowAllScopes, client_registry).await
 }
 
 const VALID_RESPONSE_TYPE_PARAM: (&str, &str) = ("response_type", "code");
@@ -16,15 +36,32 @@ const VALID_SCOPE_PARAM: (&str, &str) = ("scope", "scope_a scope_b");
 const VALID_STATE_PARAM: (&str, &str) = ("state", "opaque");
 
 fn valid_redirect_uri() -> RedirectUri {
-    RedirectUri::new(url::Url::parse("https://example.org/foo/bar?hey=now").unwrap()).unwrap()
+    RedirectUri::new(
+        url::Url::parse("https://example.org/foo/bar?hey=now").unwrap(),
+        VALID_CLIENT_ID_PARAM.1,
+        &AllowAllRedirectUris,
+    )
+    .unwrap()
+}
+
+/// A [`ClientRegistry`] that only accepts one exact `client_id`/`redirect_uri` pair.
+struct FixedClientRegistry {
+    client_id: &'static str,
+    redirect_uri: url::Url,
+}
+
+impl ClientRegistry for FixedClientRegistry {
+    fn is_registered_redirect_uri(&self, client_id: &str, redirect_uri: &url::Url) -> bool {
+        client_id == self.client_id && redirect_uri == &self.redirect_uri
+    }
 }
 
 fn valid_scope() -> ScopeList {
     ScopeList::try_from("scope_a scope_b").unwrap()
 }
 
-#[test]
-fn trivial_query() {
+#[tokio::test]
+async fn trivial_query() {
     let trivial_query = serde_urlencoded::to_string([
         VALID_RESPONSE_TYPE_PARAM,
         VALID_CLIENT_ID_PARAM,
@@ -40,22 +77,29 @@ fn trivial_query() {
         redirect_uri: valid_redirect_uri(),
         scope: valid_scope(),
         state: None,
+        code_challenge: None,
+        code_challenge_method: None,
+        response_mode: ResponseMode::Query,
+        nonce: None,
+        prompt: None,
+        max_age: None,
+        display: None,
     };
 
     assert_eq!(
-        parse_authorization_query(&trivial_query).unwrap(),
+        parse_authorization_query(&trivial_query).await.unwrap(),
         trivial_auth_query
     );
 }
 
-#[test]
-fn missing_parameters() {
+#[tokio::test]
+async fn missing_parameters() {
     // missins response type
     let missing_response_type =
         serde_urlencoded::to_string([VALID_CLIENT_ID_PARAM, VALID_REDIRECT_URI_PARAM]).unwrap();
 
     assert_eq!(
-        parse_authorization_query(&missing_response_type),
+        parse_authorization_query(&missing_response_type).await,
         Err(Error::MissingParameter(
             Params::ResponseType.name(),
             valid_redirect_uri()
@@ -71,23 +115,21 @@ fn missing_parameters() {
     .unwrap();
 
     assert_eq!(
-        parse_authorization_query(&missing_response_type),
+        parse_authorization_query(&missing_response_type).await,
         Err(Error::MissingParameter(
             Params::ResponseType.name(),
             valid_redirect_uri()
         ))
     );
 
-    // missing client id
+    // missing client id: resolved before redirect_uri can be trusted, so this is InvalidUri
+    // rather than a MissingParameter redirect; see the from_query doc comment.
     let missing_client_id =
         serde_urlencoded::to_string([VALID_RESPONSE_TYPE_PARAM, VALID_REDIRECT_URI_PARAM]).unwrap();
 
     assert_eq!(
-        parse_authorization_query(&missing_client_id),
-        Err(Error::MissingParameter(
-            Params::ClientId.name(),
-            valid_redirect_uri()
-        ))
+        parse_authorization_query(&missing_client_id).await,
+        Err(Error::InvalidUri)
     );
 
     let missing_client_id = serde_urlencoded::to_string([
@@ -98,11 +140,8 @@ fn missing_parameters() {
     .unwrap();
 
     assert_eq!(
-        parse_authorization_query(&missing_client_id),
-        Err(Error::MissingParameter(
-            Params::ClientId.name(),
-            valid_redirect_uri()
-        ))
+        parse_authorization_query(&missing_client_id).await,
+        Err(Error::InvalidUri)
     );
 
     // missing redirect_uri
@@ -110,7 +149,7 @@ fn missing_parameters() {
         serde_urlencoded::to_string([VALID_RESPONSE_TYPE_PARAM, VALID_CLIENT_ID_PARAM]).unwrap();
 
     assert_eq!(
-        parse_authorization_query(&missing_redirect_uri),
+        parse_authorization_query(&missing_redirect_uri).await,
         Err(Error::InvalidUri)
     );
 
@@ -122,13 +161,13 @@ fn missing_parameters() {
     .unwrap();
 
     assert_eq!(
-        parse_authorization_query(&missing_redirect_uri),
+        parse_authorization_query(&missing_redirect_uri).await,
         Err(Error::InvalidUri)
     );
 }
 
-#[test]
-fn invalid_parameters() {
+#[tokio::test]
+async fn invalid_parameters() {
     let invalid_redirect_uri = serde_urlencoded::to_string([
         VALID_RESPONSE_TYPE_PARAM,
         VALID_CLIENT_ID_PARAM,
@@ -138,13 +177,13 @@ fn invalid_parameters() {
     .unwrap();
 
     assert_eq!(
-        parse_authorization_query(&invalid_redirect_uri),
+        parse_authorization_query(&invalid_redirect_uri).await,
         Err(Error::InvalidUri)
     );
 }
 
-#[test]
-fn invalid_scope() {
+#[tokio::test]
+async fn invalid_scope() {
     let invalid_scope = serde_urlencoded::to_string([
         VALID_RESPONSE_TYPE_PARAM,
         VALID_CLIENT_ID_PARAM,
@@ -154,7 +193,7 @@ fn invalid_scope() {
     .unwrap();
 
     assert_eq!(
-        parse_authorization_query(&invalid_scope),
+        parse_authorization_query(&invalid_scope).await,
         Err(Error::InvalidScope(
             "invalid_scope_à".to_owned(),
             valid_redirect_uri()
@@ -162,8 +201,8 @@ fn invalid_scope() {
     );
 }
 
-#[test]
-fn ignore_opaque_parameters() {
+#[tokio::test]
+async fn ignore_opaque_parameters() {
     let repeated_opaque_params = serde_urlencoded::to_string([
         ("repeated1", "hey"),
         VALID_RESPONSE_TYPE_PARAM,
@@ -190,16 +229,23 @@ fn ignore_opaque_parameters() {
         redirect_uri: valid_redirect_uri(),
         scope: valid_scope(),
         state: None,
+        code_challenge: None,
+        code_challenge_method: None,
+        response_mode: ResponseMode::Query,
+        nonce: None,
+        prompt: None,
+        max_age: None,
+        display: None,
     };
 
     assert_eq!(
-        parse_authorization_query(&repeated_opaque_params).unwrap(),
+        parse_authorization_query(&repeated_opaque_params).await.unwrap(),
         repeated_opaque_params_query
     );
 }
 
-#[test]
-fn repeated_parameters() {
+#[tokio::test]
+async fn repeated_parameters() {
     let repeated_response_type = serde_urlencoded::to_string([
         VALID_RESPONSE_TYPE_PARAM,
         ("response_type", ""),
@@ -211,10 +257,12 @@ fn repeated_parameters() {
     .unwrap();
 
     assert_eq!(
-        parse_authorization_query(&repeated_response_type),
+        parse_authorization_query(&repeated_response_type).await,
         Err(Error::RepeatedParameter(valid_redirect_uri()))
     );
 
+    // like redirect_uri, client_id is resolved before a trustworthy redirect_uri exists to
+    // redirect errors to, so a repeated client_id is also InvalidUri rather than a redirect.
     let repeated_client_id = serde_urlencoded::to_string([
         VALID_RESPONSE_TYPE_PARAM,
         VALID_CLIENT_ID_PARAM,
@@ -226,8 +274,8 @@ fn repeated_parameters() {
     .unwrap();
 
     assert_eq!(
-        parse_authorization_query(&repeated_client_id),
-        Err(Error::RepeatedParameter(valid_redirect_uri()))
+        parse_authorization_query(&repeated_client_id).await,
+        Err(Error::InvalidUri)
     );
 
     let repeated_redirect_uri = serde_urlencoded::to_string([
@@ -241,7 +289,7 @@ fn repeated_parameters() {
     .unwrap();
 
     assert_eq!(
-        parse_authorization_query(&repeated_redirect_uri),
+        parse_authorization_query(&repeated_redirect_uri).await,
         Err(Error::InvalidUri)
     );
 
@@ -256,7 +304,7 @@ fn repeated_parameters() {
     .unwrap();
 
     assert_eq!(
-        parse_authorization_query(&repeated_scope),
+        parse_authorization_query(&repeated_scope).await,
         Err(Error::RepeatedParameter(valid_redirect_uri()))
     );
 
@@ -271,13 +319,13 @@ fn repeated_parameters() {
     .unwrap();
 
     assert_eq!(
-        parse_authorization_query(&repeated_state),
+        parse_authorization_query(&repeated_state).await,
         Err(Error::RepeatedParameter(valid_redirect_uri()))
     );
 }
 
-#[test]
-fn unsupported_response_type() {
+#[tokio::test]
+async fn unsupported_response_type() {
     let unsupported_response_type = serde_urlencoded::to_string([
         VALID_STATE_PARAM,
         ("response_type", "not_code"),
@@ -288,7 +336,852 @@ fn unsupported_response_type() {
     .unwrap();
 
     assert_eq!(
-        parse_authorization_query(&unsupported_response_type),
+        parse_authorization_query(&unsupported_response_type).await,
         Err(Error::UnsupportedResponseType(valid_redirect_uri()))
     );
 }
+
+#[tokio::test]
+async fn code_challenge() {
+    let valid_verifier = "a".repeat(43);
+
+    let with_code_challenge = serde_urlencoded::to_string([
+        VALID_RESPONSE_TYPE_PARAM,
+        VALID_CLIENT_ID_PARAM,
+        VALID_REDIRECT_URI_PARAM,
+        VALID_SCOPE_PARAM,
+        ("code_challenge", valid_verifier.as_str()),
+    ])
+    .unwrap();
+
+    let with_code_challenge_query = AuthorizationRequestQuery {
+        opaque_parameters: OpaqueParameters(vec![]),
+        response_type: ResponseType::new("code").unwrap(),
+        client_id: VALID_CLIENT_ID_PARAM.1.to_owned(),
+        redirect_uri: valid_redirect_uri(),
+        scope: valid_scope(),
+        state: None,
+        code_challenge: Some(CodeChallenge::new(&valid_verifier, &valid_redirect_uri()).unwrap()),
+        code_challenge_method: Some(CodeChallengeMethod::default_for_challenge()),
+        response_mode: ResponseMode::Query,
+        nonce: None,
+        prompt: None,
+        max_age: None,
+        display: None,
+    };
+
+    assert_eq!(
+        parse_authorization_query(&with_code_challenge).await.unwrap(),
+        with_code_challenge_query
+    );
+}
+
+#[tokio::test]
+async fn invalid_code_challenge() {
+    let too_short = serde_urlencoded::to_string([
+        VALID_RESPONSE_TYPE_PARAM,
+        VALID_CLIENT_ID_PARAM,
+        VALID_REDIRECT_URI_PARAM,
+        VALID_SCOPE_PARAM,
+        ("code_challenge", "too_short"),
+    ])
+    .unwrap();
+
+    assert_eq!(
+        parse_authorization_query(&too_short).await,
+        Err(Error::InvalidCodeChallenge(
+            "too_short".to_owned(),
+            valid_redirect_uri()
+        ))
+    );
+}
+
+#[tokio::test]
+async fn invalid_code_challenge_method() {
+    let valid_verifier = "a".repeat(43);
+
+    let unknown_method = serde_urlencoded::to_string([
+        VALID_RESPONSE_TYPE_PARAM,
+        VALID_CLIENT_ID_PARAM,
+        VALID_REDIRECT_URI_PARAM,
+        VALID_SCOPE_PARAM,
+        ("code_challenge", valid_verifier.as_str()),
+        ("code_challenge_method", "unknown"),
+    ])
+    .unwrap();
+
+    assert_eq!(
+        parse_authorization_query(&unknown_method).await,
+        Err(Error::InvalidCodeChallengeMethod(
+            "unknown".to_owned(),
+            valid_redirect_uri()
+        ))
+    );
+}
+
+#[tokio::test]
+async fn code_challenge_method_without_code_challenge() {
+    let method_only = serde_urlencoded::to_string([
+        VALID_RESPONSE_TYPE_PARAM,
+        VALID_CLIENT_ID_PARAM,
+        VALID_REDIRECT_URI_PARAM,
+        VALID_SCOPE_PARAM,
+        ("code_challenge_method", "S256"),
+    ])
+    .unwrap();
+
+    assert_eq!(
+        parse_authorization_query(&method_only).await,
+        Err(Error::CodeChallengeMethodWithoutChallenge(
+            valid_redirect_uri()
+        ))
+    );
+}
+
+#[test]
+fn code_challenge_verify_plain() {
+    let verifier = "a".repeat(43);
+    let challenge = CodeChallenge::new(&verifier, &valid_redirect_uri()).unwrap();
+
+    assert!(challenge.verify(&verifier, CodeChallengeMethod::Plain));
+    assert!(!challenge.verify(&"b".repeat(43), CodeChallengeMethod::Plain));
+}
+
+#[test]
+fn code_challenge_verify_s256() {
+    use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+    use sha2::{Digest, Sha256};
+
+    let verifier = "a".repeat(43);
+    let expected_challenge = URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()));
+    let challenge = CodeChallenge::new(&expected_challenge, &valid_redirect_uri()).unwrap();
+
+    assert!(challenge.verify(&verifier, CodeChallengeMethod::S256));
+    assert!(!challenge.verify(&"b".repeat(43), CodeChallengeMethod::S256));
+}
+
+fn unverified_jwt(claims_json: &str) -> String {
+    use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+    format!("header.{}.signature", URL_SAFE_NO_PAD.encode(claims_json))
+}
+
+#[tokio::test]
+async fn request_object_conflict() {
+    let both = serde_urlencoded::to_string([
+        VALID_RESPONSE_TYPE_PARAM,
+        VALID_CLIENT_ID_PARAM,
+        VALID_REDIRECT_URI_PARAM,
+        VALID_SCOPE_PARAM,
+        ("request", "irrelevant"),
+        ("request_uri", "https://example.org/request.jwt"),
+    ])
+    .unwrap();
+
+    assert_eq!(
+        parse_authorization_query(&both).await,
+        Err(Error::RequestObjectConflict(valid_redirect_uri()))
+    );
+}
+
+#[tokio::test]
+async fn request_object_is_rejected_without_signature_verification() {
+    // Even a well-formed, semantically valid Request Object is rejected: there is no client key
+    // registry to verify its signature against yet, so none of its claims (here, a `client_id`
+    // that does match the plain query parameter) are ever trusted or even inspected.
+    let jwt = unverified_jwt(r#"{"client_id":"valid_client_id","state":"from_jwt"}"#);
+
+    let with_request = serde_urlencoded::to_string([
+        VALID_RESPONSE_TYPE_PARAM,
+        VALID_CLIENT_ID_PARAM,
+        VALID_REDIRECT_URI_PARAM,
+        VALID_SCOPE_PARAM,
+        ("request", jwt.as_str()),
+    ])
+    .unwrap();
+
+    assert_eq!(
+        parse_authorization_query(&with_request).await,
+        Err(Error::InvalidRequestObject(valid_redirect_uri()))
+    );
+}
+
+#[tokio::test]
+async fn response_mode_fragment() {
+    let with_response_mode = serde_urlencoded::to_string([
+        VALID_RESPONSE_TYPE_PARAM,
+        VALID_CLIENT_ID_PARAM,
+        VALID_REDIRECT_URI_PARAM,
+        VALID_SCOPE_PARAM,
+        ("response_mode", "fragment"),
+    ])
+    .unwrap();
+
+    let result = parse_authorization_query(&with_response_mode).await.unwrap();
+
+    assert_eq!(result.response_mode, ResponseMode::Fragment);
+}
+
+#[tokio::test]
+async fn response_mode_form_post() {
+    let with_response_mode = serde_urlencoded::to_string([
+        VALID_RESPONSE_TYPE_PARAM,
+        VALID_CLIENT_ID_PARAM,
+        VALID_REDIRECT_URI_PARAM,
+        VALID_SCOPE_PARAM,
+        ("response_mode", "form_post"),
+    ])
+    .unwrap();
+
+    let result = parse_authorization_query(&with_response_mode).await.unwrap();
+
+    assert_eq!(result.response_mode, ResponseMode::FormPost);
+}
+
+#[test]
+fn default_response_mode_for_code_is_query() {
+    let response_type = ResponseType::new("code").unwrap();
+
+    assert_eq!(ResponseMode::default_for_response_type(&response_type), ResponseMode::Query);
+}
+
+#[test]
+fn default_response_mode_for_token_is_fragment() {
+    let response_type = ResponseType::new("token").unwrap();
+
+    assert_eq!(ResponseMode::default_for_response_type(&response_type), ResponseMode::Fragment);
+}
+
+#[test]
+fn default_response_mode_for_code_id_token_is_fragment() {
+    let response_type = ResponseType::new("code id_token").unwrap();
+
+    assert_eq!(ResponseMode::default_for_response_type(&response_type), ResponseMode::Fragment);
+}
+
+#[test]
+fn render_success_query_appends_to_existing_query_string() {
+    let params = [("code".to_owned(), "abc".to_owned())];
+
+    let rendered = ResponseMode::Query.render_success_body(&valid_redirect_uri(), &params);
+
+    assert_eq!(
+        rendered,
+        RenderedSuccess::Redirect("https://example.org/foo/bar?hey=now&code=abc".to_owned())
+    );
+}
+
+#[test]
+fn render_success_fragment_is_appended_after_hash() {
+    let params = [("code".to_owned(), "abc".to_owned()), ("state".to_owned(), "xyz".to_owned())];
+
+    let rendered = ResponseMode::Fragment.render_success_body(&valid_redirect_uri(), &params);
+
+    assert_eq!(
+        rendered,
+        RenderedSuccess::Redirect(
+            "https://example.org/foo/bar?hey=now#code=abc&state=xyz".to_owned()
+        )
+    );
+}
+
+#[test]
+fn render_success_form_post_embeds_escaped_hidden_inputs() {
+    let params = [("code".to_owned(), "\"><script>".to_owned())];
+
+    let rendered = ResponseMode::FormPost.render_success_body(&valid_redirect_uri(), &params);
+
+    let RenderedSuccess::Html(body) = rendered else {
+        panic!("form_post must render an HTML body, not a redirect");
+    };
+    assert!(body.contains(r#"action="https://example.org/foo/bar?hey=now""#));
+    assert!(body.contains(r#"name="code" value="&quot;&gt;&lt;script&gt;""#));
+    assert!(!body.contains("<script>"));
+}
+
+#[tokio::test]
+async fn unknown_scope() {
+    let query = serde_urlencoded::to_string([
+        VALID_RESPONSE_TYPE_PARAM,
+        VALID_CLIENT_ID_PARAM,
+        VALID_REDIRECT_URI_PARAM,
+        VALID_SCOPE_PARAM,
+    ])
+    .unwrap();
+
+    let registry = StaticScopeRegistry::new(["scope_a"]);
+
+    assert_eq!(
+        parse_authorization_query_with_registry(&query, &registry).await,
+        Err(Error::UnknownScope(
+            "scope_b".to_owned(),
+            valid_redirect_uri()
+        ))
+    );
+}
+
+#[tokio::test]
+async fn known_scope() {
+    let query = serde_urlencoded::to_string([
+        VALID_RESPONSE_TYPE_PARAM,
+        VALID_CLIENT_ID_PARAM,
+        VALID_REDIRECT_URI_PARAM,
+        VALID_SCOPE_PARAM,
+    ])
+    .unwrap();
+
+    let registry = StaticScopeRegistry::new(["scope_a", "scope_b"]);
+
+    assert_eq!(
+        parse_authorization_query_with_registry(&query, &registry)
+            .await
+            .unwrap()
+            .scope,
+        valid_scope()
+    );
+}
+
+#[tokio::test]
+async fn registered_redirect_uri() {
+    let query = serde_urlencoded::to_string([
+        VALID_RESPONSE_TYPE_PARAM,
+        VALID_CLIENT_ID_PARAM,
+        VALID_REDIRECT_URI_PARAM,
+        VALID_SCOPE_PARAM,
+    ])
+    .unwrap();
+
+    let registry = FixedClientRegistry {
+        client_id: VALID_CLIENT_ID_PARAM.1,
+        redirect_uri: url::Url::parse(VALID_REDIRECT_URI_PARAM.1).unwrap(),
+    };
+
+    assert_eq!(
+        parse_authorization_query_with_client_registry(&query, &registry)
+            .await
+            .unwrap()
+            .redirect_uri,
+        valid_redirect_uri()
+    );
+}
+
+#[tokio::test]
+async fn unregistered_redirect_uri() {
+    let query = serde_urlencoded::to_string([
+        VALID_RESPONSE_TYPE_PARAM,
+        VALID_CLIENT_ID_PARAM,
+        VALID_REDIRECT_URI_PARAM,
+        VALID_SCOPE_PARAM,
+    ])
+    .unwrap();
+
+    let registry = FixedClientRegistry {
+        client_id: VALID_CLIENT_ID_PARAM.1,
+        redirect_uri: url::Url::parse("https://example.org/other").unwrap(),
+    };
+
+    assert_eq!(
+        parse_authorization_query_with_client_registry(&query, &registry).await,
+        Err(Error::InvalidRedirectUri(
+            RedirectUriRejectionReason::Unregistered
+        ))
+    );
+}
+
+#[tokio::test]
+async fn redirect_uri_registered_for_different_client() {
+    let query = serde_urlencoded::to_string([
+        VALID_RESPONSE_TYPE_PARAM,
+        VALID_CLIENT_ID_PARAM,
+        VALID_REDIRECT_URI_PARAM,
+        VALID_SCOPE_PARAM,
+    ])
+    .unwrap();
+
+    let registry = FixedClientRegistry {
+        client_id: "other_client_id",
+        redirect_uri: url::Url::parse(VALID_REDIRECT_URI_PARAM.1).unwrap(),
+    };
+
+    assert_eq!(
+        parse_authorization_query_with_client_registry(&query, &registry).await,
+        Err(Error::InvalidRedirectUri(
+            RedirectUriRejectionReason::Unregistered
+        ))
+    );
+}
+
+#[tokio::test]
+async fn redirect_uri_must_be_absolute() {
+    let query = serde_urlencoded::to_string([
+        VALID_RESPONSE_TYPE_PARAM,
+        VALID_CLIENT_ID_PARAM,
+        ("redirect_uri", "mailto:someone@example.org"),
+        VALID_SCOPE_PARAM,
+    ])
+    .unwrap();
+
+    assert_eq!(
+        parse_authorization_query(&query).await,
+        Err(Error::InvalidRedirectUri(
+            RedirectUriRejectionReason::NotAbsolute
+        ))
+    );
+}
+
+#[tokio::test]
+async fn redirect_uri_must_not_have_a_fragment() {
+    let query = serde_urlencoded::to_string([
+        VALID_RESPONSE_TYPE_PARAM,
+        VALID_CLIENT_ID_PARAM,
+        ("redirect_uri", "https://example.org/foo/bar#fragment"),
+        VALID_SCOPE_PARAM,
+    ])
+    .unwrap();
+
+    assert_eq!(
+        parse_authorization_query(&query).await,
+        Err(Error::InvalidRedirectUri(
+            RedirectUriRejectionReason::HasFragment
+        ))
+    );
+}
+
+#[tokio::test]
+async fn redirect_uri_scheme_must_be_allowed() {
+    let query = serde_urlencoded::to_string([
+        VALID_RESPONSE_TYPE_PARAM,
+        VALID_CLIENT_ID_PARAM,
+        ("redirect_uri", "ftp://example.org/foo/bar"),
+        VALID_SCOPE_PARAM,
+    ])
+    .unwrap();
+
+    assert_eq!(
+        parse_authorization_query(&query).await,
+        Err(Error::InvalidRedirectUri(
+            RedirectUriRejectionReason::DisallowedScheme("ftp".to_owned())
+        ))
+    );
+}
+
+#[tokio::test]
+async fn redirect_uri_http_is_rejected_for_non_loopback_hosts() {
+    let query = serde_urlencoded::to_string([
+        VALID_RESPONSE_TYPE_PARAM,
+        VALID_CLIENT_ID_PARAM,
+        ("redirect_uri", "http://example.org/foo/bar"),
+        VALID_SCOPE_PARAM,
+    ])
+    .unwrap();
+
+    assert_eq!(
+        parse_authorization_query(&query).await,
+        Err(Error::InvalidRedirectUri(
+            RedirectUriRejectionReason::DisallowedScheme("http".to_owned())
+        ))
+    );
+}
+
+#[tokio::test]
+async fn redirect_uri_http_is_allowed_for_loopback_hosts() {
+    let query = serde_urlencoded::to_string([
+        VALID_RESPONSE_TYPE_PARAM,
+        VALID_CLIENT_ID_PARAM,
+        ("redirect_uri", "http://127.0.0.1:8080/callback"),
+        VALID_SCOPE_PARAM,
+    ])
+    .unwrap();
+
+    assert_eq!(
+        parse_authorization_query(&query)
+            .await
+            .unwrap()
+            .redirect_uri
+            .get()
+            .as_str(),
+        "http://127.0.0.1:8080/callback"
+    );
+}
+
+#[tokio::test]
+async fn unsupported_response_mode() {
+    let unsupported_response_mode = serde_urlencoded::to_string([
+        VALID_RESPONSE_TYPE_PARAM,
+        VALID_CLIENT_ID_PARAM,
+        VALID_REDIRECT_URI_PARAM,
+        VALID_SCOPE_PARAM,
+        ("response_mode", "not_a_mode"),
+    ])
+    .unwrap();
+
+    assert_eq!(
+        parse_authorization_query(&unsupported_response_mode).await,
+        Err(Error::UnsupportedResponseMode(valid_redirect_uri()))
+    );
+}
+
+#[tokio::test]
+async fn static_client_registry_accepts_exact_match() {
+    let query = serde_urlencoded::to_string([
+        VALID_RESPONSE_TYPE_PARAM,
+        VALID_CLIENT_ID_PARAM,
+        VALID_REDIRECT_URI_PARAM,
+        VALID_SCOPE_PARAM,
+    ])
+    .unwrap();
+
+    let registry = StaticClientRegistry::new([(
+        VALID_CLIENT_ID_PARAM.1.to_owned(),
+        ClientRegistration::new([url::Url::parse(VALID_REDIRECT_URI_PARAM.1).unwrap()]),
+    )]);
+
+    assert_eq!(
+        parse_authorization_query_with_client_registry(&query, &registry)
+            .await
+            .unwrap()
+            .redirect_uri,
+        valid_redirect_uri()
+    );
+}
+
+#[tokio::test]
+async fn static_client_registry_rejects_unknown_client() {
+    let query = serde_urlencoded::to_string([
+        VALID_RESPONSE_TYPE_PARAM,
+        VALID_CLIENT_ID_PARAM,
+        VALID_REDIRECT_URI_PARAM,
+        VALID_SCOPE_PARAM,
+    ])
+    .unwrap();
+
+    let registry = StaticClientRegistry::new([(
+        "other_client_id".to_owned(),
+        ClientRegistration::new([url::Url::parse(VALID_REDIRECT_URI_PARAM.1).unwrap()]),
+    )]);
+
+    assert_eq!(
+        parse_authorization_query_with_client_registry(&query, &registry).await,
+        Err(Error::InvalidRedirectUri(
+            RedirectUriRejectionReason::Unregistered
+        ))
+    );
+}
+
+#[tokio::test]
+async fn static_client_registry_rejects_mismatched_port() {
+    let query = serde_urlencoded::to_string([
+        VALID_RESPONSE_TYPE_PARAM,
+        VALID_CLIENT_ID_PARAM,
+        ("redirect_uri", "https://example.org:8443/foo/bar?hey=now"),
+        VALID_SCOPE_PARAM,
+    ])
+    .unwrap();
+
+    let registry = StaticClientRegistry::new([(
+        VALID_CLIENT_ID_PARAM.1.to_owned(),
+        ClientRegistration::new([url::Url::parse(VALID_REDIRECT_URI_PARAM.1).unwrap()]),
+    )]);
+
+    assert_eq!(
+        parse_authorization_query_with_client_registry(&query, &registry).await,
+        Err(Error::InvalidRedirectUri(
+            RedirectUriRejectionReason::Unregistered
+        ))
+    );
+}
+
+#[tokio::test]
+async fn static_client_registry_rejects_path_prefix() {
+    let query = serde_urlencoded::to_string([
+        VALID_RESPONSE_TYPE_PARAM,
+        VALID_CLIENT_ID_PARAM,
+        ("redirect_uri", "https://example.org/foo/bar/extra?hey=now"),
+        VALID_SCOPE_PARAM,
+    ])
+    .unwrap();
+
+    let registry = StaticClientRegistry::new([(
+        VALID_CLIENT_ID_PARAM.1.to_owned(),
+        ClientRegistration::new([url::Url::parse(VALID_REDIRECT_URI_PARAM.1).unwrap()]),
+    )]);
+
+    assert_eq!(
+        parse_authorization_query_with_client_registry(&query, &registry).await,
+        Err(Error::InvalidRedirectUri(
+            RedirectUriRejectionReason::Unregistered
+        ))
+    );
+}
+
+#[test]
+fn response_type_order_is_not_significant() {
+    assert_eq!(
+        ResponseType::new("code token").unwrap(),
+        ResponseType::new("token code").unwrap()
+    );
+}
+
+#[test]
+fn response_type_accepts_every_combination() {
+    for response_type in [
+        "code",
+        "token",
+        "id_token",
+        "code id_token",
+        "code token",
+        "id_token token",
+        "code id_token token",
+    ] {
+        assert!(
+            ResponseType::new(response_type).is_ok(),
+            "{response_type:?} should have been accepted"
+        );
+    }
+}
+
+#[test]
+fn response_type_rejects_duplicate_values() {
+    assert_eq!(ResponseType::new("code code"), Err(()));
+}
+
+#[test]
+fn response_type_rejects_unknown_values() {
+    assert_eq!(ResponseType::new("not_code"), Err(()));
+}
+
+#[test]
+fn response_type_accessors_reflect_requested_values() {
+    let response_type = ResponseType::new("code id_token").unwrap();
+
+    assert!(response_type.has_code());
+    assert!(response_type.has_id_token());
+    assert!(!response_type.has_token());
+}
+
+#[tokio::test]
+async fn nonce_is_preserved() {
+    let query = serde_urlencoded::to_string([
+        VALID_RESPONSE_TYPE_PARAM,
+        VALID_CLIENT_ID_PARAM,
+        VALID_REDIRECT_URI_PARAM,
+        VALID_SCOPE_PARAM,
+        ("nonce", "opaque_nonce"),
+    ])
+    .unwrap();
+
+    let result = parse_authorization_query(&query).await.unwrap();
+
+    assert_eq!(result.nonce, Some("opaque_nonce".to_owned()));
+}
+
+#[tokio::test]
+async fn empty_nonce_means_unsent() {
+    let query = serde_urlencoded::to_string([
+        VALID_RESPONSE_TYPE_PARAM,
+        VALID_CLIENT_ID_PARAM,
+        VALID_REDIRECT_URI_PARAM,
+        VALID_SCOPE_PARAM,
+        ("nonce", ""),
+    ])
+    .unwrap();
+
+    let result = parse_authorization_query(&query).await.unwrap();
+
+    assert_eq!(result.nonce, None);
+}
+
+#[tokio::test]
+async fn repeated_nonce_is_an_error() {
+    let query = serde_urlencoded::to_string([
+        VALID_RESPONSE_TYPE_PARAM,
+        VALID_CLIENT_ID_PARAM,
+        VALID_REDIRECT_URI_PARAM,
+        VALID_SCOPE_PARAM,
+        ("nonce", "first"),
+        ("nonce", "second"),
+    ])
+    .unwrap();
+
+    assert_eq!(
+        parse_authorization_query(&query).await,
+        Err(Error::RepeatedParameter(valid_redirect_uri()))
+    );
+}
+
+#[test]
+fn prompt_accepts_every_single_value() {
+    for prompt in ["none", "login", "consent", "select_account"] {
+        assert!(
+            PromptSet::new(prompt, &valid_redirect_uri()).is_ok(),
+            "{prompt:?} should have been accepted"
+        );
+    }
+}
+
+#[test]
+fn prompt_accepts_combinations_without_none() {
+    let prompt = PromptSet::new("login consent select_account", &valid_redirect_uri()).unwrap();
+
+    assert!(prompt.has_login());
+    assert!(prompt.has_consent());
+    assert!(prompt.has_select_account());
+    assert!(!prompt.has_none());
+}
+
+#[test]
+fn prompt_rejects_none_combined_with_other_values() {
+    assert_eq!(
+        PromptSet::new("none login", &valid_redirect_uri()),
+        Err(Error::PromptNoneCombined(valid_redirect_uri()))
+    );
+}
+
+#[test]
+fn prompt_rejects_duplicate_values() {
+    assert_eq!(
+        PromptSet::new("login login", &valid_redirect_uri()),
+        Err(Error::InvalidPrompt(
+            "login login".to_owned(),
+            valid_redirect_uri()
+        ))
+    );
+}
+
+#[test]
+fn prompt_rejects_unknown_values() {
+    assert_eq!(
+        PromptSet::new("not_a_prompt", &valid_redirect_uri()),
+        Err(Error::InvalidPrompt(
+            "not_a_prompt".to_owned(),
+            valid_redirect_uri()
+        ))
+    );
+}
+
+#[tokio::test]
+async fn prompt_none_is_rejected_with_login_required() {
+    // This server never has an end-user session, so it can never satisfy `prompt=none` (which
+    // forbids displaying any authentication or consent UI) without a session to check against.
+    let query = serde_urlencoded::to_string([
+        VALID_RESPONSE_TYPE_PARAM,
+        VALID_CLIENT_ID_PARAM,
+        VALID_REDIRECT_URI_PARAM,
+        VALID_SCOPE_PARAM,
+        ("prompt", "none"),
+    ])
+    .unwrap();
+
+    assert_eq!(
+        parse_authorization_query(&query).await,
+        Err(Error::LoginRequired(valid_redirect_uri()))
+    );
+}
+
+#[tokio::test]
+async fn prompt_none_combined_is_rejected_at_query_level() {
+    let query = serde_urlencoded::to_string([
+        VALID_RESPONSE_TYPE_PARAM,
+        VALID_CLIENT_ID_PARAM,
+        VALID_REDIRECT_URI_PARAM,
+        VALID_SCOPE_PARAM,
+        ("prompt", "none consent"),
+    ])
+    .unwrap();
+
+    assert_eq!(
+        parse_authorization_query(&query).await,
+        Err(Error::PromptNoneCombined(valid_redirect_uri()))
+    );
+}
+
+#[tokio::test]
+async fn max_age_is_parsed() {
+    let query = serde_urlencoded::to_string([
+        VALID_RESPONSE_TYPE_PARAM,
+        VALID_CLIENT_ID_PARAM,
+        VALID_REDIRECT_URI_PARAM,
+        VALID_SCOPE_PARAM,
+        ("max_age", "3600"),
+    ])
+    .unwrap();
+
+    let result = parse_authorization_query(&query).await.unwrap();
+
+    assert_eq!(result.max_age, Some(3600));
+}
+
+#[tokio::test]
+async fn negative_max_age_is_rejected() {
+    let query = serde_urlencoded::to_string([
+        VALID_RESPONSE_TYPE_PARAM,
+        VALID_CLIENT_ID_PARAM,
+        VALID_REDIRECT_URI_PARAM,
+        VALID_SCOPE_PARAM,
+        ("max_age", "-1"),
+    ])
+    .unwrap();
+
+    assert_eq!(
+        parse_authorization_query(&query).await,
+        Err(Error::InvalidMaxAge(
+            "-1".to_owned(),
+            valid_redirect_uri()
+        ))
+    );
+}
+
+#[tokio::test]
+async fn non_numeric_max_age_is_rejected() {
+    let query = serde_urlencoded::to_string([
+        VALID_RESPONSE_TYPE_PARAM,
+        VALID_CLIENT_ID_PARAM,
+        VALID_REDIRECT_URI_PARAM,
+        VALID_SCOPE_PARAM,
+        ("max_age", "soon"),
+    ])
+    .unwrap();
+
+    assert_eq!(
+        parse_authorization_query(&query).await,
+        Err(Error::InvalidMaxAge(
+            "soon".to_owned(),
+            valid_redirect_uri()
+        ))
+    );
+}
+
+#[tokio::test]
+async fn display_is_parsed() {
+    let query = serde_urlencoded::to_string([
+        VALID_RESPONSE_TYPE_PARAM,
+        VALID_CLIENT_ID_PARAM,
+        VALID_REDIRECT_URI_PARAM,
+        VALID_SCOPE_PARAM,
+        ("display", "popup"),
+    ])
+    .unwrap();
+
+    let result = parse_authorization_query(&query).await.unwrap();
+
+    assert_eq!(result.display, Some(Display::Popup));
+}
+
+#[tokio::test]
+async fn unsupported_display_is_rejected() {
+    let query = serde_urlencoded::to_string([
+        VALID_RESPONSE_TYPE_PARAM,
+        VALID_CLIENT_ID_PARAM,
+        VALID_REDIRECT_URI_PARAM,
+        VALID_SCOPE_PARAM,
+        ("display", "not_a_display"),
+    ])
+    .unwrap();
+
+    assert_eq!(
+        parse_authorization_query(&query).await,
+        Err(Error::InvalidDisplay(
+            "not_a_display".to_owned(),
+            valid_redirect_uri()
+        ))
+    );
+}
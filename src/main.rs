@@ -1,17 +1,28 @@
 use axum::{routing, Router};
+use poem_openapi::OpenApiService;
 use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 
+mod api;
 mod auth;
+mod metadata;
+mod oauth_error;
+mod opaque_parameters;
+mod query;
+mod scope;
+mod token;
 
 #[tokio::main]
 async fn main() -> color_eyre::eyre::Result<()> {
     #[derive(OpenApi)]
     #[openapi(
         paths(
-            auth::authorization
+            auth::authorization,
+            metadata::metadata,
+            token::token
         ),
         components(
+            schemas(metadata::Metadata, token::TokenRequestBody, token::TokenResponse)
         ),
         tags(
             (name = "auth", description = "OAuth 2.0 authentication API")
@@ -31,12 +42,30 @@ async fn main() -> color_eyre::eyre::Result<()> {
 
     let app = Router::new()
         .route("/authorization", routing::get(auth::authorization))
+        .route("/token", routing::post(token::token))
+        .route(metadata::WELL_KNOWN_PATH, routing::get(metadata::metadata))
         .merge(SwaggerUi::new("/swagger-ui").url("/api-docs/openapi.json", ApiDoc::openapi()));
 
-    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
+    // The poem counterpart of `app` above, built on the parsing core in `query` instead of
+    // `auth::query`. Served on its own port alongside the axum `Router` rather than in place of
+    // it, since nothing yet picks one implementation over the other.
+    let poem_api_service =
+        OpenApiService::new(api::Api, "AstronAuth (poem)", "0.1").server("http://127.0.0.1:3001");
+    let poem_swagger_ui = poem_api_service.swagger_ui();
+    let poem_app = poem::Route::new()
+        .nest("/", poem_api_service)
+        .nest("/swagger-ui", poem_swagger_ui);
+
+    let axum_listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
         .await
         .unwrap();
-    axum::serve(listener, app).await.unwrap();
+    let poem_listener = poem::listener::TcpListener::bind("127.0.0.1:3001");
+
+    tokio::try_join!(
+        axum::serve(axum_listener, app),
+        poem::Server::new(poem_listener).run(poem_app)
+    )
+    .unwrap();
 
     Ok(())
 }
@@ -0,0 +1,120 @@
+//! Serves the OAuth 2.0 Authorization Server Metadata document, as defined by
+//! [RFC 8414](https://datatracker.ietf.org/doc/html/rfc8414).
+
+use axum::Json;
+use serde::Serialize;
+use url::Url;
+
+use crate::auth::{
+    AllowAllScopes, ScopeRegistry, SUPPORTED_CODE_CHALLENGE_METHODS, SUPPORTED_RESPONSE_TYPES,
+};
+
+#[cfg(test)]
+mod test;
+
+/// The well-known path this document is served at.
+pub const WELL_KNOWN_PATH: &str = "/.well-known/oauth-authorization-server";
+
+/// Why a candidate `issuer` was rejected.
+#[derive(Debug, PartialEq, Eq, Clone, thiserror::Error)]
+pub enum InvalidIssuer {
+    #[error("issuer must be an absolute URI with a scheme and host")]
+    NotAbsolute,
+    #[error("issuer must use the https scheme, per RFC 8414 section 2")]
+    NotHttps,
+    #[error("issuer must not contain a query component")]
+    HasQuery,
+    #[error("issuer must not contain a fragment component")]
+    HasFragment,
+}
+
+/// An authorization server's `issuer` identifier, as defined by [RFC 8414 section
+/// 2](https://datatracker.ietf.org/doc/html/rfc8414#section-2): an `https` URL with no query or
+/// fragment component.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Issuer(Url);
+
+impl Issuer {
+    /// Validates `url` the same way [`RedirectUri::new`](crate::auth::RedirectUri::new) rejects a
+    /// non-absolute URI or one with a fragment, plus the `https`-only and no-query rules RFC 8414
+    /// additionally imposes on `issuer`.
+    pub fn new(url: Url) -> Result<Self, InvalidIssuer> {
+        if url.cannot_be_a_base() || url.host_str().is_none() {
+            return Err(InvalidIssuer::NotAbsolute);
+        }
+        if url.scheme() != "https" {
+            return Err(InvalidIssuer::NotHttps);
+        }
+        if url.query().is_some() {
+            return Err(InvalidIssuer::HasQuery);
+        }
+        if url.fragment().is_some() {
+            return Err(InvalidIssuer::HasFragment);
+        }
+
+        Ok(Self(url))
+    }
+
+    /// Joins `path` onto this issuer's URL, e.g. `issuer.join("token")` for an issuer of
+    /// `https://example.org` yields `https://example.org/token` rather than the malformed
+    /// `https://example.org//token` that `format!("{issuer}/{path}")` would produce once
+    /// [`Display`](std::fmt::Display) serializes the trailing slash `Url` always adds to the root
+    /// path.
+    fn join(&self, path: &str) -> String {
+        self.0.join(path).expect("relative path is always a valid URL").to_string()
+    }
+}
+
+impl std::fmt::Display for Issuer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema, poem_openapi::Object)]
+pub struct Metadata {
+    issuer: String,
+    authorization_endpoint: String,
+    token_endpoint: String,
+    scopes_supported: Vec<String>,
+    response_types_supported: Vec<String>,
+    grant_types_supported: Vec<String>,
+    code_challenge_methods_supported: Vec<String>,
+}
+
+impl Metadata {
+    /// Builds the metadata document for a server reachable at `issuer`.
+    ///
+    /// `response_types_supported` and `code_challenge_methods_supported` are copied from the
+    /// `SUPPORTED_RESPONSE_TYPES` and `SUPPORTED_CODE_CHALLENGE_METHODS` lists, which are kept in
+    /// sync by hand with what the authorization endpoint's parser accepts, and `scopes_supported`
+    /// comes from the same `scope_registry` it validates requests against.
+    pub(crate) fn new(issuer: &Issuer, scope_registry: &dyn ScopeRegistry) -> Self {
+        fn to_strings(values: &[&str]) -> Vec<String> {
+            values.iter().map(|value| value.to_string()).collect()
+        }
+
+        Self {
+            issuer: issuer.to_string(),
+            authorization_endpoint: issuer.join("authorization"),
+            token_endpoint: issuer.join("token"),
+            scopes_supported: scope_registry.known_scopes(),
+            response_types_supported: to_strings(SUPPORTED_RESPONSE_TYPES),
+            grant_types_supported: vec!["authorization_code".to_owned()],
+            code_challenge_methods_supported: to_strings(SUPPORTED_CODE_CHALLENGE_METHODS),
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/.well-known/oauth-authorization-server",
+    responses(
+        (status = 200, description = "Authorization server metadata.", body = Metadata)
+    )
+)]
+pub async fn metadata() -> Json<Metadata> {
+    // TODO: Derive the issuer and scope registry from server configuration once one exists.
+    let issuer = Issuer::new(Url::parse("https://127.0.0.1:3000").unwrap()).unwrap();
+    Json(Metadata::new(&issuer, &AllowAllScopes))
+}
@@ -0,0 +1,47 @@
+use super::{InvalidIssuer, Issuer, Metadata};
+use crate::auth::AllowAllScopes;
+
+#[test]
+fn valid_issuer() {
+    let url = url::Url::parse("https://example.org").unwrap();
+
+    assert_eq!(Issuer::new(url.clone()).unwrap().to_string(), url.to_string());
+}
+
+#[test]
+fn issuer_must_be_absolute() {
+    let url = url::Url::parse("mailto:someone@example.org").unwrap();
+
+    assert_eq!(Issuer::new(url), Err(InvalidIssuer::NotAbsolute));
+}
+
+#[test]
+fn issuer_must_use_https() {
+    let url = url::Url::parse("http://example.org").unwrap();
+
+    assert_eq!(Issuer::new(url), Err(InvalidIssuer::NotHttps));
+}
+
+#[test]
+fn issuer_must_not_have_a_query() {
+    let url = url::Url::parse("https://example.org?foo=bar").unwrap();
+
+    assert_eq!(Issuer::new(url), Err(InvalidIssuer::HasQuery));
+}
+
+#[test]
+fn issuer_must_not_have_a_fragment() {
+    let url = url::Url::parse("https://example.org#fragment").unwrap();
+
+    assert_eq!(Issuer::new(url), Err(InvalidIssuer::HasFragment));
+}
+
+#[test]
+fn endpoints_are_joined_without_a_double_slash() {
+    let issuer = Issuer::new(url::Url::parse("https://example.org").unwrap()).unwrap();
+
+    let metadata = Metadata::new(&issuer, &AllowAllScopes);
+
+    assert_eq!(metadata.authorization_endpoint, "https://example.org/authorization");
+    assert_eq!(metadata.token_endpoint, "https://example.org/token");
+}
@@ -0,0 +1,67 @@
+//! Shared rendering of an OAuth 2.0 authorization error, as defined by [RFC 6749 section
+//! 4.1.2.1](https://datatracker.ietf.org/doc/html/rfc6749#section-4.1.2.1): a plain `400` when no
+//! `redirect_uri` can be trusted yet, or a `303` redirect carrying `error` in the query string.
+//! Both the axum and poem implementations of the authorization endpoint need to make this same
+//! decision; this module makes it once so they can't drift from each other, and so each
+//! framework's `IntoResponse`/`ResponseError` impl only has to adapt the result into its own
+//! response type.
+
+use url::Url;
+
+/// The concrete shape of an [`AuthorizationError`], once it's been decided whether `redirect_uri`
+/// was trustworthy enough to redirect to.
+pub enum AuthorizationErrorKind {
+    /// No `redirect_uri` was known yet, or it couldn't be rendered back into a query string.
+    BadRequest,
+    /// `redirect_uri`, with `error` (and whatever it already carried) added to its query string.
+    Redirect { location: String },
+}
+
+/// A framework-independent authorization error response.
+pub struct AuthorizationError {
+    pub kind: AuthorizationErrorKind,
+    pub body: String,
+}
+
+/// Builds the response for `body` (the originating parsing error's `Display` text), redirecting
+/// to `redirect_uri` with `standard_error_text` set as its `error` query parameter when
+/// `redirect_uri` is already known to be trustworthy.
+pub fn render_error_response(
+    redirect_uri: Option<&Url>,
+    standard_error_text: &str,
+    body: String,
+) -> AuthorizationError {
+    let Some(redirect_uri) = redirect_uri else {
+        return AuthorizationError {
+            kind: AuthorizationErrorKind::BadRequest,
+            body,
+        };
+    };
+
+    let bad_request = || AuthorizationError {
+        kind: AuthorizationErrorKind::BadRequest,
+        body: body.clone(),
+    };
+
+    let mut query: Vec<(String, String)> =
+        match serde_urlencoded::from_str(redirect_uri.query().unwrap_or_default()) {
+            Ok(query) => query,
+            Err(_) => return bad_request(),
+        };
+    query.push(("error".to_owned(), standard_error_text.to_owned()));
+
+    let query = match serde_urlencoded::to_string(query) {
+        Ok(query) => query,
+        Err(_) => return bad_request(),
+    };
+
+    let mut redirect_uri = redirect_uri.clone();
+    redirect_uri.set_query(Some(&query));
+
+    AuthorizationError {
+        kind: AuthorizationErrorKind::Redirect {
+            location: redirect_uri.to_string(),
+        },
+        body,
+    }
+}
@@ -1,17 +1,25 @@
-//! This module is necessary to handle the requirements the RFC has for preserving the query
+//! The poem counterpart of [`crate::auth::query`]: parses the authorization request query and
+//! preserves the RFC-mandated round-tripping of opaque parameters, `redirect_uri`, `scope` and
+//! `state`, the way the [`Api`](crate::api::Api) built on [`poem_openapi`] needs.
 
-use itertools::{Either, Itertools};
+use itertools::Either;
 use poem::{
     error::ResponseError,
     http::{StatusCode, Uri},
     FromRequest, Request, Response,
 };
+use std::str::FromStr;
 use tracing::instrument;
 
 #[cfg(test)]
 mod test;
 
+mod opaque_parameters;
 mod parsing;
+mod scope;
+
+use self::opaque_parameters::OpaqueParameters;
+use self::scope::ScopeList;
 
 trait QueryParams: std::str::FromStr {
     fn name(&self) -> &'static str;
@@ -33,6 +41,10 @@ enum AuthorizationQueryParams {
     RedirectUri,
     Scope,
     State,
+    CodeChallenge,
+    CodeChallengeMethod,
+    Request,
+    RequestUri,
 }
 
 impl std::str::FromStr for AuthorizationQueryParams {
@@ -45,6 +57,10 @@ impl std::str::FromStr for AuthorizationQueryParams {
             "redirect_uri" => Ok(Self::RedirectUri),
             "scope" => Ok(Self::Scope),
             "state" => Ok(Self::State),
+            "code_challenge" => Ok(Self::CodeChallenge),
+            "code_challenge_method" => Ok(Self::CodeChallengeMethod),
+            "request" => Ok(Self::Request),
+            "request_uri" => Ok(Self::RequestUri),
             _ => Err(()),
         }
     }
@@ -58,6 +74,10 @@ impl QueryParams for AuthorizationQueryParams {
             Self::RedirectUri => "redirect_uri",
             Self::Scope => "scope",
             Self::State => "state",
+            Self::CodeChallenge => "code_challenge",
+            Self::CodeChallengeMethod => "code_challenge_method",
+            Self::Request => "request",
+            Self::RequestUri => "request_uri",
         }
     }
 
@@ -68,6 +88,10 @@ impl QueryParams for AuthorizationQueryParams {
             "redirect_uri",
             "scope",
             "state",
+            "code_challenge",
+            "code_challenge_method",
+            "request",
+            "request_uri",
         ]
     }
 
@@ -78,6 +102,10 @@ impl QueryParams for AuthorizationQueryParams {
             Self::RedirectUri,
             Self::Scope,
             Self::State,
+            Self::CodeChallenge,
+            Self::CodeChallengeMethod,
+            Self::Request,
+            Self::RequestUri,
         ]
     }
 }
@@ -85,125 +113,332 @@ impl QueryParams for AuthorizationQueryParams {
 pub type ResponseType = String;
 pub type ClientId = String;
 pub type RedirectUri = Uri;
-pub type ScopeList = Vec<Scope>;
 pub type State = String;
 
-/// A scope is a valid scope according to
-/// [section 3.3](https://datatracker.ietf.org/doc/html/rfc6749#section-3.3)
-/// of the RFC
-#[derive(PartialEq, Eq)]
-pub struct Scope(String);
+/// Schemes a `redirect_uri` may use unconditionally.
+// TODO: Source this from app configuration once a server-wide policy store exists.
+const ALLOWED_REDIRECT_URI_SCHEMES: &[&str] = &["https"];
+
+/// Loopback hosts for which `http` is additionally accepted, to support native/dev clients that
+/// cannot terminate TLS locally; see [RFC 8252 section
+/// 7.3](https://datatracker.ietf.org/doc/html/rfc8252#section-7.3).
+const LOOPBACK_REDIRECT_URI_HOSTS: &[&str] = &["127.0.0.1", "[::1]", "localhost"];
+
+fn is_allowed_redirect_uri_scheme(uri: &url::Url) -> bool {
+    ALLOWED_REDIRECT_URI_SCHEMES.contains(&uri.scheme())
+        || (uri.scheme() == "http"
+            && uri
+                .host_str()
+                .is_some_and(|host| LOOPBACK_REDIRECT_URI_HOSTS.contains(&host)))
+}
+
+/// Why a candidate `redirect_uri` was rejected, surfaced so operators can tell which RFC 3986 /
+/// OAuth 2.0 requirement a misbehaving client is violating.
+#[derive(Debug, PartialEq, Eq, Clone, thiserror::Error)]
+pub enum RedirectUriRejectionReason {
+    #[error("redirect_uri must be an absolute URI with a scheme and host")]
+    NotAbsolute,
+    #[error("redirect_uri must not contain a fragment component")]
+    HasFragment,
+    #[error("redirect_uri scheme {0:?} is not allowed")]
+    DisallowedScheme(String),
+}
+
+/// Parses and validates a `redirect_uri` against the RFC 3986 / OAuth 2.0 rules this server
+/// enforces: it must be absolute, must not carry a fragment, and its scheme must be allowed; see
+/// [`is_allowed_redirect_uri_scheme`]. Returns the parsed `url::Url` rather than the `http::Uri`
+/// this module otherwise works with, since the error-redirect rendering in
+/// [`crate::oauth_error`] needs to manipulate its query string; the caller converts it to a `Uri`
+/// once parsing has fully succeeded.
+fn validate_redirect_uri(raw: &str) -> Result<url::Url, AuthorizationQueryParsingError> {
+    use AuthorizationQueryParsingError as Error;
+    use RedirectUriRejectionReason as Reason;
 
-impl std::fmt::Debug for Scope {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str(&self.0)
+    let parsed = url::Url::parse(raw).map_err(|_| Error::InvalidUri)?;
+
+    if parsed.cannot_be_a_base() || parsed.host_str().is_none() {
+        return Err(Error::InvalidRedirectUri(Reason::NotAbsolute));
+    }
+    if parsed.fragment().is_some() {
+        return Err(Error::InvalidRedirectUri(Reason::HasFragment));
     }
+    if !is_allowed_redirect_uri_scheme(&parsed) {
+        return Err(Error::InvalidRedirectUri(Reason::DisallowedScheme(
+            parsed.scheme().to_owned(),
+        )));
+    }
+
+    Ok(parsed)
 }
 
-impl TryFrom<&str> for Scope {
-    type Error = AuthorizationQueryParsingError;
+pub type CodeChallenge = String;
 
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
-        if value.len() == 0 {
-            return Err(Self::Error::InvalidScope(value.to_owned()));
-        }
+/// Method used to derive `code_challenge` from the `code_verifier`, as defined by
+/// [RFC 7636](https://datatracker.ietf.org/doc/html/rfc7636#section-4.3).
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum CodeChallengeMethod {
+    Plain,
+    S256,
+}
 
-        let all_valid = value.as_bytes().iter().all(|char| {
-            *char == 0x21 || (0x23..=0x5B).contains(char) || (0x5D..=0x7E).contains(char)
-        });
+impl CodeChallengeMethod {
+    fn default_for_challenge() -> Self {
+        Self::Plain
+    }
 
-        if !(all_valid) {
-            return Err(Self::Error::InvalidScope(value.to_owned()));
+    fn new(
+        code_challenge_method: &str,
+        redirect_uri: &url::Url,
+    ) -> Result<Self, AuthorizationQueryParsingError> {
+        match code_challenge_method {
+            "plain" => Ok(Self::Plain),
+            "S256" => Ok(Self::S256),
+            _ => Err(AuthorizationQueryParsingError::InvalidCodeChallengeMethod(
+                code_challenge_method.to_owned(),
+                redirect_uri.clone(),
+            )),
         }
+    }
+}
+
+/// Whether `code_verifier` is syntactically valid per
+/// [RFC 7636 section 4.1](https://datatracker.ietf.org/doc/html/rfc7636#section-4.1): the same
+/// 43-128 character unreserved-set grammar `code_challenge` itself is held to.
+fn is_valid_code_verifier(code_verifier: &str) -> bool {
+    let valid_length = (43..=128).contains(&code_verifier.len());
+    let valid_chars = code_verifier
+        .bytes()
+        .all(|byte| byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~'));
+
+    valid_length && valid_chars
+}
 
-        Ok(Self(value.to_owned()))
+fn validate_code_challenge(
+    code_challenge: String,
+    redirect_uri: &url::Url,
+) -> Result<CodeChallenge, AuthorizationQueryParsingError> {
+    if !is_valid_code_verifier(&code_challenge) {
+        return Err(AuthorizationQueryParsingError::InvalidCodeChallenge(
+            code_challenge,
+            redirect_uri.clone(),
+        ));
     }
+
+    Ok(code_challenge)
 }
 
-impl Into<String> for Scope {
-    fn into(self) -> String {
-        self.0
+/// Verifies a `code_verifier` presented at the token endpoint against a stored `code_challenge`,
+/// as defined in [RFC 7636 section 4.6](https://datatracker.ietf.org/doc/html/rfc7636#section-4.6):
+/// for `S256`, `code_verifier` is hashed and compared in constant time; for `plain`, the RFC
+/// defines the transformation as the identity, so the verifier and the stored challenge are
+/// compared directly.
+pub fn verify_code_challenge(
+    code_challenge: &str,
+    code_verifier: &str,
+    method: CodeChallengeMethod,
+) -> bool {
+    use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+    use sha2::{Digest, Sha256};
+    use subtle::ConstantTimeEq;
+
+    if !is_valid_code_verifier(code_verifier) {
+        return false;
+    }
+
+    match method {
+        CodeChallengeMethod::Plain => code_verifier == code_challenge,
+        CodeChallengeMethod::S256 => {
+            let digest = Sha256::digest(code_verifier.as_bytes());
+            let encoded = URL_SAFE_NO_PAD.encode(digest);
+            encoded.as_bytes().ct_eq(code_challenge.as_bytes()).into()
+        }
     }
 }
 
-/// Extracs all params not contained in non_opaque_keys.
-/// Returns (opaque_parameters, remaining_parameters)
-fn extract_opaque_parameters<T: QueryParams>(
-    parameters: Vec<(String, String)>,
-) -> (Vec<(String, String)>, Vec<(T, String)>) {
-    return parameters
-        .into_iter()
-        .partition_map(|key_value| T::split(key_value));
+/// Claims carried by a `request`/`request_uri` JWT, as defined by
+/// [RFC 9101](https://datatracker.ietf.org/doc/html/rfc9101). Any claim present here overrides
+/// the matching plain query parameter.
+#[derive(Debug, Default, serde::Deserialize)]
+struct RequestObjectClaims {
+    client_id: Option<String>,
+    redirect_uri: Option<String>,
+    response_type: Option<String>,
+    scope: Option<String>,
+    state: Option<String>,
+    code_challenge: Option<String>,
+    code_challenge_method: Option<String>,
+}
+
+/// Unconditionally rejects every Request Object: there is no client key registry to verify a
+/// signature against yet, and applying claims decoded from an unverified payload would defeat the
+/// "integrity-protected, non-tamperable" property that is the entire point of RFC 9101 Request
+/// Objects. `request`/`request_uri` stay rejected until signature verification exists.
+// TODO: Verify the JWT signature against the client's registered key once client key management
+// exists, then actually decode and return the claims instead of rejecting unconditionally.
+fn decode_request_object_claims(
+    _jwt: &str,
+    redirect_uri: &url::Url,
+) -> Result<RequestObjectClaims, AuthorizationQueryParsingError> {
+    use AuthorizationQueryParsingError as Error;
+
+    Err(Error::InvalidRequestObject(redirect_uri.clone()))
+}
+
+/// Fetches the `request` JWT referenced by a `request_uri` parameter.
+///
+/// `request_uri` must be an HTTPS URL, per RFC 9101 section 5.
+// TODO: Verify the JWT signature against the client's registered key once client key management
+// exists.
+async fn fetch_request_object(
+    request_uri: &str,
+    redirect_uri: &url::Url,
+) -> Result<String, AuthorizationQueryParsingError> {
+    use AuthorizationQueryParsingError as Error;
+
+    let url = url::Url::parse(request_uri)
+        .map_err(|_| Error::UnreachableRequestUri(redirect_uri.clone()))?;
+
+    if url.scheme() != "https" {
+        return Err(Error::UnreachableRequestUri(redirect_uri.clone()));
+    }
+
+    reqwest::get(url)
+        .await
+        .map_err(|_| Error::UnreachableRequestUri(redirect_uri.clone()))?
+        .text()
+        .await
+        .map_err(|_| Error::UnreachableRequestUri(redirect_uri.clone()))
 }
 
 /// Represents the authorization request query.
 #[derive(Debug, PartialEq, Eq)]
 pub struct AuthorizationRequestQuery {
     /// All the parameters that aren't client, redirect_uri, scope and state are preserved as-is.
-    opaque_parameters: Vec<(String, String)>,
+    opaque_parameters: OpaqueParameters,
     pub client_id: ClientId,
     pub redirect_uri: RedirectUri,
     pub scope: ScopeList,
     pub state: Option<State>,
+    /// PKCE code challenge, see [RFC 7636](https://datatracker.ietf.org/doc/html/rfc7636).
+    pub code_challenge: Option<CodeChallenge>,
+    /// Defaults to [`CodeChallengeMethod::Plain`] when [`Self::code_challenge`] is set but no
+    /// method was given. `None` when no `code_challenge` was sent at all.
+    pub code_challenge_method: Option<CodeChallengeMethod>,
 }
 
 #[derive(Debug, thiserror::Error, PartialEq)]
 #[error("authorization query parsing error")]
 pub enum AuthorizationQueryParsingError {
     #[error("only the \"code\" response_type is supported")]
-    UnsupportedResponseType,
+    UnsupportedResponseType(url::Url),
     #[error("{0}")]
     ParsingError(#[from] parsing::ParsingError),
     #[error("missing parameter {0:?}")]
-    MissingParameter(&'static str),
+    MissingParameter(&'static str, url::Url),
     #[error(
         "invalid scope format on `{0:?}`. check section 3.3 of RFC 6749 for the allowed characters"
     )]
-    InvalidScope(String),
+    InvalidScope(String, url::Url),
     #[error("the redirect_uri is invalid")]
     InvalidUri,
+    #[error("the redirect_uri is invalid: {0}")]
+    InvalidRedirectUri(RedirectUriRejectionReason),
+    #[error("invalid code_challenge `{0:?}`. check RFC 7636 section 4.2 for the allowed format")]
+    InvalidCodeChallenge(String, url::Url),
+    #[error("invalid code_challenge_method `{0:?}`. only \"plain\" and \"S256\" are supported")]
+    InvalidCodeChallengeMethod(String, url::Url),
+    #[error("code_challenge_method was sent without a code_challenge")]
+    CodeChallengeMethodWithoutChallenge(url::Url),
+    #[error("\"request\" and \"request_uri\" are mutually exclusive")]
+    RequestObjectConflict(url::Url),
+    #[error("the request object is not a valid JWT")]
+    InvalidRequestObject(url::Url),
+    #[error("the request_uri could not be fetched")]
+    UnreachableRequestUri(url::Url),
+    #[error("the client_id claim of the request object does not match the client_id parameter")]
+    ClientIdMismatch(url::Url),
+    #[error("the redirect_uri claim of the request object is invalid")]
+    InvalidRequestObjectRedirectUri(url::Url),
 }
 
 impl AuthorizationQueryParsingError {
     fn standard_error_text(&self) -> &'static str {
         match self {
-            Self::UnsupportedResponseType => "unsupported_response_type",
-            Self::MissingParameter(_) | Self::ParsingError(_) => "invalid_request",
-            Self::InvalidScope(_) => "invalid_scope",
-            Self::InvalidUri => "server_error",
+            Self::UnsupportedResponseType(_) => "unsupported_response_type",
+            Self::MissingParameter(_, _) | Self::ParsingError(_) => "invalid_request",
+            Self::InvalidScope(_, _) => "invalid_scope",
+            Self::InvalidUri | Self::InvalidRedirectUri(_) => "server_error",
+            Self::InvalidCodeChallenge(_, _)
+            | Self::InvalidCodeChallengeMethod(_, _)
+            | Self::CodeChallengeMethodWithoutChallenge(_) => "invalid_request",
+            Self::RequestObjectConflict(_)
+            | Self::InvalidRequestObject(_)
+            | Self::UnreachableRequestUri(_)
+            | Self::ClientIdMismatch(_)
+            | Self::InvalidRequestObjectRedirectUri(_) => "invalid_request",
+        }
+    }
+
+    /// The `redirect_uri` an error redirects to, or `None` for the errors that precede its
+    /// validation and so can only ever surface as a plain `400`.
+    fn redirect_uri(&self) -> Option<&url::Url> {
+        match self {
+            Self::InvalidUri | Self::InvalidRedirectUri(_) | Self::ParsingError(_) => None,
+            Self::UnsupportedResponseType(redirect_uri)
+            | Self::MissingParameter(_, redirect_uri)
+            | Self::InvalidScope(_, redirect_uri)
+            | Self::InvalidCodeChallenge(_, redirect_uri)
+            | Self::InvalidCodeChallengeMethod(_, redirect_uri)
+            | Self::CodeChallengeMethodWithoutChallenge(redirect_uri)
+            | Self::RequestObjectConflict(redirect_uri)
+            | Self::InvalidRequestObject(redirect_uri)
+            | Self::UnreachableRequestUri(redirect_uri)
+            | Self::ClientIdMismatch(redirect_uri)
+            | Self::InvalidRequestObjectRedirectUri(redirect_uri) => Some(redirect_uri),
         }
     }
 }
 
 impl ResponseError for AuthorizationQueryParsingError {
-    // TODO: Implement actual statuses
     fn status(&self) -> StatusCode {
-        match self {
-            Self::MissingParameter(param)
-                if *param == AuthorizationQueryParams::RedirectUri.name() =>
-            {
-                StatusCode::BAD_REQUEST
-            }
-            Self::InvalidUri => StatusCode::BAD_REQUEST,
-            _ => StatusCode::SEE_OTHER,
+        match self.redirect_uri() {
+            Some(_) => StatusCode::SEE_OTHER,
+            None => StatusCode::BAD_REQUEST,
         }
     }
 
     fn as_response(&self) -> Response {
-        let status = self.status();
+        let crate::oauth_error::AuthorizationError { kind, body } =
+            crate::oauth_error::render_error_response(
+                self.redirect_uri(),
+                self.standard_error_text(),
+                self.to_string(),
+            );
+
+        let (status, location) = match kind {
+            crate::oauth_error::AuthorizationErrorKind::BadRequest => {
+                (StatusCode::BAD_REQUEST, None)
+            }
+            crate::oauth_error::AuthorizationErrorKind::Redirect { location } => {
+                (StatusCode::SEE_OTHER, Some(location))
+            }
+        };
+
         let response_builder = Response::builder().status(status);
-        let response_builder = match status {
-            StatusCode::SEE_OTHER => response_builder.header("Location", "todo"),
-            StatusCode::BAD_REQUEST | _ => response_builder,
+        let response_builder = match location {
+            Some(location) => response_builder.header("Location", location),
+            None => response_builder,
         };
-        response_builder.body(self.to_string())
+        response_builder.body(body)
     }
 }
 
-impl std::str::FromStr for AuthorizationRequestQuery {
-    type Err = AuthorizationQueryParsingError;
+impl AuthorizationRequestQuery {
     /// Tries to generate itself from a still percentage-encoded string.
-    fn from_str(query: &str) -> Result<Self, Self::Err> {
+    ///
+    /// Async because resolving a `request_uri` Request Object (see
+    /// [RFC 9101](https://datatracker.ietf.org/doc/html/rfc9101)) requires an HTTP fetch.
+    async fn from_query(query: &str) -> Result<Self, AuthorizationQueryParsingError> {
         use AuthorizationQueryParams as Params;
         use AuthorizationQueryParsingError as Error;
 
@@ -216,9 +451,13 @@ impl std::str::FromStr for AuthorizationRequestQuery {
             redirect_uri: Option<String>,
             scope: Option<String>,
             state: Option<String>,
+            code_challenge: Option<String>,
+            code_challenge_method: Option<String>,
+            request: Option<String>,
+            request_uri: Option<String>,
         }
 
-        let optional_self = result.into_iter().fold(
+        let mut optional_self = result.into_iter().fold(
             OptionalSelf::default(),
             |optional_self, (variant, optional_param)| match variant {
                 Params::ResponseType => OptionalSelf {
@@ -241,55 +480,139 @@ impl std::str::FromStr for AuthorizationRequestQuery {
                     state: optional_param,
                     ..optional_self
                 },
+                Params::CodeChallenge => OptionalSelf {
+                    code_challenge: optional_param,
+                    ..optional_self
+                },
+                Params::CodeChallengeMethod => OptionalSelf {
+                    code_challenge_method: optional_param,
+                    ..optional_self
+                },
+                Params::Request => OptionalSelf {
+                    request: optional_param,
+                    ..optional_self
+                },
+                Params::RequestUri => OptionalSelf {
+                    request_uri: optional_param,
+                    ..optional_self
+                },
             },
         );
 
         tracing::trace!("Finished parsing query: {:?}", optional_self);
 
+        // client_id and redirect_uri must be resolved before anything else can be trusted to
+        // build an error redirect, so they're handled first; see the equivalent ordering (and
+        // comment) in the axum implementation's `from_query`. Every error variant below this
+        // point carries the now-trustworthy redirect_uri.
+        let client_id = match optional_self.client_id {
+            Some(client_id) => client_id,
+            None => return Err(Error::InvalidUri),
+        };
+
+        let redirect_uri = match optional_self.redirect_uri {
+            Some(redirect_uri) => redirect_uri,
+            None => return Err(Error::InvalidUri),
+        };
+        let mut redirect_uri = validate_redirect_uri(&redirect_uri)?;
+
+        // Request Objects (RFC 9101): fields carried inside a signed JWT take precedence over the
+        // plain query parameters they duplicate, but `client_id` must still be sent as a plain
+        // query parameter and must match the claim inside the object.
+        match (&optional_self.request, &optional_self.request_uri) {
+            (Some(_), Some(_)) => return Err(Error::RequestObjectConflict(redirect_uri)),
+            (None, None) => {}
+            (request, request_uri) => {
+                let jwt = match request {
+                    Some(request) => request.clone(),
+                    None => {
+                        fetch_request_object(request_uri.as_deref().unwrap(), &redirect_uri)
+                            .await?
+                    }
+                };
+
+                let claims = decode_request_object_claims(&jwt, &redirect_uri)?;
+
+                match &claims.client_id {
+                    Some(claim_client_id) if claim_client_id == &client_id => {}
+                    _ => return Err(Error::ClientIdMismatch(redirect_uri)),
+                }
+
+                // A `redirect_uri` claim takes precedence the same as every other field here, but
+                // it must be re-validated the same way the plain-query `redirect_uri` was above;
+                // see the equivalent handling in the axum implementation's `from_query`.
+                if let Some(claim_redirect_uri) = &claims.redirect_uri {
+                    redirect_uri = validate_redirect_uri(claim_redirect_uri).map_err(|_| {
+                        Error::InvalidRequestObjectRedirectUri(redirect_uri.clone())
+                    })?;
+                }
+
+                optional_self.response_type =
+                    claims.response_type.or(optional_self.response_type);
+                optional_self.scope = claims.scope.or(optional_self.scope);
+                optional_self.state = claims.state.or(optional_self.state);
+                optional_self.code_challenge =
+                    claims.code_challenge.or(optional_self.code_challenge);
+                optional_self.code_challenge_method = claims
+                    .code_challenge_method
+                    .or(optional_self.code_challenge_method);
+            }
+        }
+
         match optional_self.response_type {
-            None => return Err(Error::MissingParameter(Params::ResponseType.name())),
+            None => {
+                return Err(Error::MissingParameter(
+                    Params::ResponseType.name(),
+                    redirect_uri,
+                ))
+            }
             Some(s) if s != "code" => {
-                return Err(Error::UnsupportedResponseType);
+                return Err(Error::UnsupportedResponseType(redirect_uri));
             }
             // Some and s == code
             _ => {}
         }
 
-        let client_id = match optional_self.client_id {
-            Some(client_id) => client_id,
-            None => return Err(Error::MissingParameter(Params::ClientId.name())),
-        };
+        let scope = ScopeList::try_from(&optional_self.scope.unwrap_or_default() as &str)
+            .map_err(|invalid_scope| Error::InvalidScope(invalid_scope.0, redirect_uri.clone()))?;
 
-        let redirect_uri = match optional_self.redirect_uri {
-            Some(redirect_uri) => redirect_uri,
-            None => return Err(Error::MissingParameter(Params::RedirectUri.name())),
-        };
-        let redirect_uri = Uri::from_str(&redirect_uri).map_err(|_err| Error::InvalidUri)?;
+        if optional_self.code_challenge.is_none() && optional_self.code_challenge_method.is_some() {
+            return Err(Error::CodeChallengeMethodWithoutChallenge(redirect_uri));
+        }
 
-        let scope = optional_self
-            .scope
-            .unwrap_or_default()
-            .split(' ')
-            .map(Scope::try_from)
-            .collect::<Result<_, _>>()?;
+        let code_challenge = optional_self
+            .code_challenge
+            .map(|code_challenge| validate_code_challenge(code_challenge, &redirect_uri))
+            .transpose()?;
+
+        let code_challenge_method = optional_self
+            .code_challenge_method
+            .map(|code_challenge_method| {
+                CodeChallengeMethod::new(&code_challenge_method, &redirect_uri)
+            })
+            .transpose()?
+            .or(code_challenge
+                .is_some()
+                .then(CodeChallengeMethod::default_for_challenge));
+
+        let redirect_uri = Uri::from_str(redirect_uri.as_str()).map_err(|_| Error::InvalidUri)?;
 
         let result = AuthorizationRequestQuery {
-            opaque_parameters,
+            opaque_parameters: OpaqueParameters(opaque_parameters),
             client_id,
             redirect_uri,
-            scope: scope,
+            scope,
             state: optional_self.state,
+            code_challenge,
+            code_challenge_method,
         };
 
         tracing::trace!("Resulted in: {:?}", result);
 
         Ok(result)
     }
-}
 
-/// Implementations for parsing
-impl AuthorizationRequestQuery {
-    /// Simply maps to `Self::try_from_query`
+    /// Simply maps to `Self::from_query`
     #[instrument(name = "parse_authorization_query", skip_all)]
     async fn internal_from_request(req: &Request) -> Result<Self, AuthorizationQueryParsingError> {
         // this string will be percent-encoded. we'll have to decode it!
@@ -297,7 +620,7 @@ impl AuthorizationRequestQuery {
 
         tracing::trace!("Started to parse query: {:?}", query);
 
-        let result = query.parse();
+        let result = Self::from_query(query).await;
 
         tracing::trace!("Resulted in: {:?}", result);
 
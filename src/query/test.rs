@@ -2,15 +2,20 @@ use poem::http::Uri;
 
 use super::{
     AuthorizationQueryParams as Params, AuthorizationQueryParsingError as Error,
-    AuthorizationRequestQuery, QueryParams,
+    AuthorizationRequestQuery, CodeChallengeMethod, OpaqueParameters, QueryParams,
+    RedirectUriRejectionReason, ScopeList,
 };
 
-fn parse_authorization_query(query: &str) -> Result<AuthorizationRequestQuery, Error> {
-    query.parse()
+fn valid_redirect_uri() -> url::Url {
+    url::Url::parse("https://example.org/foo/bar?hey=now&test").unwrap()
 }
 
-#[test]
-fn trivial_query() {
+async fn parse_authorization_query(query: &str) -> Result<AuthorizationRequestQuery, Error> {
+    AuthorizationRequestQuery::from_query(query).await
+}
+
+#[tokio::test]
+async fn trivial_query() {
     let trivial_query = serde_urlencoded::to_string([
         ("response_type", "code"),
         ("client_id", "valid_client_id"),
@@ -20,21 +25,23 @@ fn trivial_query() {
     .unwrap();
 
     let trivial_auth_query = AuthorizationRequestQuery {
-        opaque_parameters: vec![],
+        opaque_parameters: OpaqueParameters(vec![]),
         client_id: "valid_client_id".to_owned(),
         redirect_uri: Uri::from_static("https://example.org/foo/bar?hey=now&test"),
-        scope: vec!["scope_a".try_into().unwrap(), "scope_b".try_into().unwrap()],
+        scope: ScopeList(vec!["scope_a".try_into().unwrap(), "scope_b".try_into().unwrap()]),
         state: None,
+        code_challenge: None,
+        code_challenge_method: None,
     };
 
     assert_eq!(
-        parse_authorization_query(&trivial_query).unwrap(),
+        parse_authorization_query(&trivial_query).await.unwrap(),
         trivial_auth_query
     );
 }
 
-#[test]
-fn missing_parameters() {
+#[tokio::test]
+async fn missing_parameters() {
     // missins response type
     let missing_response_type = serde_urlencoded::to_string([
         ("client_id", "valid_client_id"),
@@ -43,8 +50,11 @@ fn missing_parameters() {
     .unwrap();
 
     assert_eq!(
-        parse_authorization_query(&missing_response_type),
-        Err(Error::MissingParameter(Params::ResponseType.name()))
+        parse_authorization_query(&missing_response_type).await,
+        Err(Error::MissingParameter(
+            Params::ResponseType.name(),
+            valid_redirect_uri()
+        ))
     );
 
     // parameters without valuesmust be treated as unsent as per section 3.1. of RFC 6749
@@ -56,11 +66,15 @@ fn missing_parameters() {
     .unwrap();
 
     assert_eq!(
-        parse_authorization_query(&missing_response_type),
-        Err(Error::MissingParameter(Params::ResponseType.name()))
+        parse_authorization_query(&missing_response_type).await,
+        Err(Error::MissingParameter(
+            Params::ResponseType.name(),
+            valid_redirect_uri()
+        ))
     );
 
-    // missing client id
+    // missing client id: resolved before redirect_uri can be trusted, so this is InvalidUri
+    // rather than a MissingParameter redirect; see the ordering comment in `from_query`.
     let missing_client_id = serde_urlencoded::to_string([
         ("response_type", "code"),
         ("redirect_uri", "https://example.org/foo/bar?hey=now&test"),
@@ -68,8 +82,8 @@ fn missing_parameters() {
     .unwrap();
 
     assert_eq!(
-        parse_authorization_query(&missing_client_id),
-        Err(Error::MissingParameter(Params::ClientId.name()))
+        parse_authorization_query(&missing_client_id).await,
+        Err(Error::InvalidUri)
     );
 
     let missing_client_id = serde_urlencoded::to_string([
@@ -80,18 +94,18 @@ fn missing_parameters() {
     .unwrap();
 
     assert_eq!(
-        parse_authorization_query(&missing_client_id),
-        Err(Error::MissingParameter(Params::ClientId.name()))
+        parse_authorization_query(&missing_client_id).await,
+        Err(Error::InvalidUri)
     );
 
-    // missing redirect_uri
+    // missing redirect_uri: same reasoning as missing client_id above.
     let missing_redirect_uri =
         serde_urlencoded::to_string([("response_type", "code"), ("client_id", "valid_client_id")])
             .unwrap();
 
     assert_eq!(
-        parse_authorization_query(&missing_redirect_uri),
-        Err(Error::MissingParameter(Params::RedirectUri.name()))
+        parse_authorization_query(&missing_redirect_uri).await,
+        Err(Error::InvalidUri)
     );
 
     let missing_redirect_uri = serde_urlencoded::to_string([
@@ -102,13 +116,13 @@ fn missing_parameters() {
     .unwrap();
 
     assert_eq!(
-        parse_authorization_query(&missing_redirect_uri),
-        Err(Error::MissingParameter(Params::RedirectUri.name()))
+        parse_authorization_query(&missing_redirect_uri).await,
+        Err(Error::InvalidUri)
     );
 }
 
-#[test]
-fn invalid_parameters() {
+#[tokio::test]
+async fn invalid_parameters() {
     let invalid_redirect_uri = serde_urlencoded::to_string([
         ("response_type", "code"),
         ("client_id", "valid_client_id"),
@@ -118,13 +132,101 @@ fn invalid_parameters() {
     .unwrap();
 
     assert_eq!(
-        parse_authorization_query(&invalid_redirect_uri),
+        parse_authorization_query(&invalid_redirect_uri).await,
         Err(Error::InvalidUri)
     );
 }
 
-#[test]
-fn invalid_scope() {
+#[tokio::test]
+async fn redirect_uri_must_be_absolute() {
+    let query = serde_urlencoded::to_string([
+        ("response_type", "code"),
+        ("client_id", "valid_client_id"),
+        ("redirect_uri", "mailto:someone@example.org"),
+        ("scope", "scope_a scope_b"),
+    ])
+    .unwrap();
+
+    assert_eq!(
+        parse_authorization_query(&query).await,
+        Err(Error::InvalidRedirectUri(
+            RedirectUriRejectionReason::NotAbsolute
+        ))
+    );
+}
+
+#[tokio::test]
+async fn redirect_uri_must_not_have_a_fragment() {
+    let query = serde_urlencoded::to_string([
+        ("response_type", "code"),
+        ("client_id", "valid_client_id"),
+        ("redirect_uri", "https://example.org/foo/bar#fragment"),
+        ("scope", "scope_a scope_b"),
+    ])
+    .unwrap();
+
+    assert_eq!(
+        parse_authorization_query(&query).await,
+        Err(Error::InvalidRedirectUri(
+            RedirectUriRejectionReason::HasFragment
+        ))
+    );
+}
+
+#[tokio::test]
+async fn redirect_uri_scheme_must_be_allowed() {
+    let query = serde_urlencoded::to_string([
+        ("response_type", "code"),
+        ("client_id", "valid_client_id"),
+        ("redirect_uri", "ftp://example.org/foo/bar"),
+        ("scope", "scope_a scope_b"),
+    ])
+    .unwrap();
+
+    assert_eq!(
+        parse_authorization_query(&query).await,
+        Err(Error::InvalidRedirectUri(
+            RedirectUriRejectionReason::DisallowedScheme("ftp".to_owned())
+        ))
+    );
+}
+
+#[tokio::test]
+async fn redirect_uri_http_is_rejected_for_non_loopback_hosts() {
+    let query = serde_urlencoded::to_string([
+        ("response_type", "code"),
+        ("client_id", "valid_client_id"),
+        ("redirect_uri", "http://example.org/foo/bar"),
+        ("scope", "scope_a scope_b"),
+    ])
+    .unwrap();
+
+    assert_eq!(
+        parse_authorization_query(&query).await,
+        Err(Error::InvalidRedirectUri(
+            RedirectUriRejectionReason::DisallowedScheme("http".to_owned())
+        ))
+    );
+}
+
+#[tokio::test]
+async fn redirect_uri_http_is_allowed_for_loopback_hosts() {
+    let query = serde_urlencoded::to_string([
+        ("response_type", "code"),
+        ("client_id", "valid_client_id"),
+        ("redirect_uri", "http://127.0.0.1:8080/callback"),
+        ("scope", "scope_a scope_b"),
+    ])
+    .unwrap();
+
+    assert_eq!(
+        parse_authorization_query(&query).await.unwrap().redirect_uri,
+        Uri::from_static("http://127.0.0.1:8080/callback")
+    );
+}
+
+#[tokio::test]
+async fn invalid_scope() {
     let invalid_scope = serde_urlencoded::to_string([
         ("response_type", "code"),
         ("client_id", "valid_client_id"),
@@ -134,13 +236,16 @@ fn invalid_scope() {
     .unwrap();
 
     assert_eq!(
-        parse_authorization_query(&invalid_scope),
-        Err(Error::InvalidScope("invalid_scope_à".to_owned()))
+        parse_authorization_query(&invalid_scope).await,
+        Err(Error::InvalidScope(
+            "invalid_scope_à".to_owned(),
+            valid_redirect_uri()
+        ))
     );
 }
 
-#[test]
-fn ignore_opaque_parameters() {
+#[tokio::test]
+async fn ignore_opaque_parameters() {
     let repeated_opaque_params = serde_urlencoded::to_string([
         ("repeated1", "hey"),
         ("response_type", "code"),
@@ -155,27 +260,29 @@ fn ignore_opaque_parameters() {
     .unwrap();
 
     let repeated_opaque_params_query = AuthorizationRequestQuery {
-        opaque_parameters: vec![
+        opaque_parameters: OpaqueParameters(vec![
             ("repeated1".to_owned(), "hey".to_owned()),
             ("repeated1".to_owned(), "".to_owned()),
             ("repeated2".to_owned(), "once".to_owned()),
             ("repeated2".to_owned(), "twice".to_owned()),
             ("repeated2".to_owned(), "thrice".to_owned()),
-        ],
+        ]),
         client_id: "valid_client_id".to_owned(),
         redirect_uri: Uri::from_static("https://example.org/foo/bar?hey=now&test"),
-        scope: vec!["scope_a".try_into().unwrap(), "scope_b".try_into().unwrap()],
+        scope: ScopeList(vec!["scope_a".try_into().unwrap(), "scope_b".try_into().unwrap()]),
         state: None,
+        code_challenge: None,
+        code_challenge_method: None,
     };
 
     assert_eq!(
-        parse_authorization_query(&repeated_opaque_params).unwrap(),
+        parse_authorization_query(&repeated_opaque_params).await.unwrap(),
         repeated_opaque_params_query
     );
 }
 
-#[test]
-fn repeated_parameters() {
+#[tokio::test]
+async fn repeated_parameters() {
     let repeated_response_type = serde_urlencoded::to_string([
         ("response_type", "code"),
         ("response_type", ""),
@@ -187,8 +294,10 @@ fn repeated_parameters() {
     .unwrap();
 
     assert_eq!(
-        parse_authorization_query(&repeated_response_type),
-        Err(Error::RepeatedParameter(Params::ResponseType.name()))
+        parse_authorization_query(&repeated_response_type).await,
+        Err(Error::ParsingError(
+            super::parsing::ParsingError::RepeatedParameter(Params::ResponseType.name())
+        ))
     );
 
     let repeated_client_id = serde_urlencoded::to_string([
@@ -202,8 +311,10 @@ fn repeated_parameters() {
     .unwrap();
 
     assert_eq!(
-        parse_authorization_query(&repeated_client_id),
-        Err(Error::RepeatedParameter(Params::ClientId.name()))
+        parse_authorization_query(&repeated_client_id).await,
+        Err(Error::ParsingError(
+            super::parsing::ParsingError::RepeatedParameter(Params::ClientId.name())
+        ))
     );
 
     let repeated_redirect_uri = serde_urlencoded::to_string([
@@ -217,8 +328,10 @@ fn repeated_parameters() {
     .unwrap();
 
     assert_eq!(
-        parse_authorization_query(&repeated_redirect_uri),
-        Err(Error::RepeatedParameter(Params::RedirectUri.name()))
+        parse_authorization_query(&repeated_redirect_uri).await,
+        Err(Error::ParsingError(
+            super::parsing::ParsingError::RepeatedParameter(Params::RedirectUri.name())
+        ))
     );
 
     let repeated_scope = serde_urlencoded::to_string([
@@ -232,8 +345,10 @@ fn repeated_parameters() {
     .unwrap();
 
     assert_eq!(
-        parse_authorization_query(&repeated_scope),
-        Err(Error::RepeatedParameter(Params::Scope.name()))
+        parse_authorization_query(&repeated_scope).await,
+        Err(Error::ParsingError(
+            super::parsing::ParsingError::RepeatedParameter(Params::Scope.name())
+        ))
     );
 
     let repeated_state = serde_urlencoded::to_string([
@@ -247,13 +362,142 @@ fn repeated_parameters() {
     .unwrap();
 
     assert_eq!(
-        parse_authorization_query(&repeated_state),
-        Err(Error::RepeatedParameter(Params::State.name()))
+        parse_authorization_query(&repeated_state).await,
+        Err(Error::ParsingError(
+            super::parsing::ParsingError::RepeatedParameter(Params::State.name())
+        ))
+    );
+}
+
+#[tokio::test]
+async fn code_challenge() {
+    let valid_verifier = "a".repeat(43);
+
+    let with_code_challenge = serde_urlencoded::to_string([
+        ("response_type", "code"),
+        ("client_id", "valid_client_id"),
+        ("redirect_uri", "https://example.org/foo/bar?hey=now&test"),
+        ("scope", "scope_a scope_b"),
+        ("code_challenge", valid_verifier.as_str()),
+    ])
+    .unwrap();
+
+    let with_code_challenge_query = AuthorizationRequestQuery {
+        opaque_parameters: OpaqueParameters(vec![]),
+        client_id: "valid_client_id".to_owned(),
+        redirect_uri: Uri::from_static("https://example.org/foo/bar?hey=now&test"),
+        scope: ScopeList(vec!["scope_a".try_into().unwrap(), "scope_b".try_into().unwrap()]),
+        state: None,
+        code_challenge: Some(valid_verifier.clone()),
+        code_challenge_method: Some(CodeChallengeMethod::Plain),
+    };
+
+    assert_eq!(
+        parse_authorization_query(&with_code_challenge).await.unwrap(),
+        with_code_challenge_query
+    );
+}
+
+#[tokio::test]
+async fn invalid_code_challenge() {
+    let too_short = serde_urlencoded::to_string([
+        ("response_type", "code"),
+        ("client_id", "valid_client_id"),
+        ("redirect_uri", "https://example.org/foo/bar?hey=now&test"),
+        ("scope", "scope_a scope_b"),
+        ("code_challenge", "too_short"),
+    ])
+    .unwrap();
+
+    assert_eq!(
+        parse_authorization_query(&too_short).await,
+        Err(Error::InvalidCodeChallenge(
+            "too_short".to_owned(),
+            valid_redirect_uri()
+        ))
+    );
+}
+
+#[tokio::test]
+async fn invalid_code_challenge_method() {
+    let valid_verifier = "a".repeat(43);
+
+    let unknown_method = serde_urlencoded::to_string([
+        ("response_type", "code"),
+        ("client_id", "valid_client_id"),
+        ("redirect_uri", "https://example.org/foo/bar?hey=now&test"),
+        ("scope", "scope_a scope_b"),
+        ("code_challenge", valid_verifier.as_str()),
+        ("code_challenge_method", "unknown"),
+    ])
+    .unwrap();
+
+    assert_eq!(
+        parse_authorization_query(&unknown_method).await,
+        Err(Error::InvalidCodeChallengeMethod(
+            "unknown".to_owned(),
+            valid_redirect_uri()
+        ))
+    );
+}
+
+#[tokio::test]
+async fn code_challenge_method_without_code_challenge() {
+    let method_only = serde_urlencoded::to_string([
+        ("response_type", "code"),
+        ("client_id", "valid_client_id"),
+        ("redirect_uri", "https://example.org/foo/bar?hey=now&test"),
+        ("scope", "scope_a scope_b"),
+        ("code_challenge_method", "S256"),
+    ])
+    .unwrap();
+
+    assert_eq!(
+        parse_authorization_query(&method_only).await,
+        Err(Error::CodeChallengeMethodWithoutChallenge(
+            valid_redirect_uri()
+        ))
     );
 }
 
 #[test]
-fn unsupported_response_type() {
+fn code_challenge_verify_plain() {
+    let verifier = "a".repeat(43);
+
+    assert!(super::verify_code_challenge(
+        &verifier,
+        &verifier,
+        CodeChallengeMethod::Plain
+    ));
+    assert!(!super::verify_code_challenge(
+        &verifier,
+        &"b".repeat(43),
+        CodeChallengeMethod::Plain
+    ));
+}
+
+#[test]
+fn code_challenge_verify_s256() {
+    use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+    use sha2::{Digest, Sha256};
+
+    let verifier = "a".repeat(43);
+    let challenge = URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()));
+
+    assert!(super::verify_code_challenge(
+        &challenge,
+        &verifier,
+        CodeChallengeMethod::S256
+    ));
+    assert!(!super::verify_code_challenge(
+        &challenge,
+        &"b".repeat(43),
+        CodeChallengeMethod::S256
+    ));
+}
+
+#[tokio::test]
+async fn unsupported_response_type() {
     let unsupported_response_type = serde_urlencoded::to_string([
         ("response_type", "not_code"),
         ("client_id", "valid_client_id"),
@@ -263,7 +507,52 @@ fn unsupported_response_type() {
     .unwrap();
 
     assert_eq!(
-        parse_authorization_query(&unsupported_response_type),
-        Err(Error::UnsupportedResponseType)
+        parse_authorization_query(&unsupported_response_type).await,
+        Err(Error::UnsupportedResponseType(valid_redirect_uri()))
+    );
+}
+
+fn unverified_jwt(claims_json: &str) -> String {
+    use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+    format!("header.{}.signature", URL_SAFE_NO_PAD.encode(claims_json))
+}
+
+#[tokio::test]
+async fn request_object_conflict() {
+    let both = serde_urlencoded::to_string([
+        ("response_type", "code"),
+        ("client_id", "valid_client_id"),
+        ("redirect_uri", "https://example.org/foo/bar?hey=now&test"),
+        ("scope", "scope_a scope_b"),
+        ("request", "irrelevant"),
+        ("request_uri", "https://example.org/request.jwt"),
+    ])
+    .unwrap();
+
+    assert_eq!(
+        parse_authorization_query(&both).await,
+        Err(Error::RequestObjectConflict(valid_redirect_uri()))
+    );
+}
+
+#[tokio::test]
+async fn request_object_is_rejected_without_signature_verification() {
+    // Even a well-formed, semantically valid Request Object is rejected: there is no client key
+    // registry to verify its signature against yet, so none of its claims (here, a `client_id`
+    // that does match the plain query parameter) are ever trusted or even inspected.
+    let jwt = unverified_jwt(r#"{"client_id":"valid_client_id","state":"from_jwt"}"#);
+
+    let with_request = serde_urlencoded::to_string([
+        ("response_type", "code"),
+        ("client_id", "valid_client_id"),
+        ("redirect_uri", "https://example.org/foo/bar?hey=now&test"),
+        ("scope", "scope_a scope_b"),
+        ("request", jwt.as_str()),
+    ])
+    .unwrap();
+
+    assert_eq!(
+        parse_authorization_query(&with_request).await,
+        Err(Error::InvalidRequestObject(valid_redirect_uri()))
     );
 }
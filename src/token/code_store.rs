@@ -0,0 +1,41 @@
+use crate::auth::RedirectUri;
+use crate::scope::ScopeList;
+
+/// The `client_id`, `redirect_uri` and `scope` bound to an authorization code when it was issued.
+///
+/// Handed back by [`CodeStore::consume`] so the token endpoint can check the grant it was
+/// presented against what was actually authorized, without having to re-derive any of it.
+pub struct IssuedAuthorization {
+    pub client_id: String,
+    pub redirect_uri: RedirectUri,
+    pub scope: ScopeList,
+    /// The `nonce` the authorization request was sent with, if any. Carried through so it can be
+    /// embedded in the ID token once this server issues one.
+    pub nonce: Option<String>,
+}
+
+/// Looks up and invalidates authorization codes issued by the `/authorization` endpoint.
+///
+/// Injected into the token endpoint the same way a `ScopeRegistry` or `ClientRegistry` is
+/// injected into query parsing, so the token endpoint never has to know how codes are actually
+/// stored.
+pub trait CodeStore: Send + Sync {
+    /// Returns the authorization bound to `code`, removing it so it cannot be redeemed twice, per
+    /// [RFC 6749 section 4.1.2](https://datatracker.ietf.org/doc/html/rfc6749#section-4.1.2).
+    /// Returns `None` if `code` was never issued, was already redeemed, or has expired.
+    fn consume(&self, code: &str) -> Option<IssuedAuthorization>;
+}
+
+/// A [`CodeStore`] that never has a code to hand back.
+///
+/// A placeholder until `/authorization` actually issues and stores codes.
+// TODO: Replace with a real store once `/authorization` issues codes instead of a placeholder
+// response.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoIssuedCodes;
+
+impl CodeStore for NoIssuedCodes {
+    fn consume(&self, _code: &str) -> Option<IssuedAuthorization> {
+        None
+    }
+}
@@ -0,0 +1,294 @@
+//! Implements the token endpoint, as defined by [RFC 6749 section
+//! 3.2](https://datatracker.ietf.org/doc/html/rfc6749#section-3.2), for the `authorization_code`
+//! grant (section 4.1.3).
+
+use axum::body::to_bytes;
+use axum::extract::{FromRequest, Request};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+mod code_store;
+
+#[cfg(test)]
+mod test;
+
+use crate::auth::RedirectUri;
+use crate::scope::{Scope, ScopeList};
+
+pub(crate) use code_store::NoIssuedCodes;
+pub use code_store::{CodeStore, IssuedAuthorization};
+
+#[derive(Debug, PartialEq)]
+enum TokenParams {
+    GrantType,
+    Code,
+    RedirectUri,
+    ClientId,
+}
+
+impl TokenParams {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::GrantType => "grant_type",
+            Self::Code => "code",
+            Self::RedirectUri => "redirect_uri",
+            Self::ClientId => "client_id",
+        }
+    }
+}
+
+/// The only `grant_type` this server accepts.
+const SUPPORTED_GRANT_TYPE: &str = "authorization_code";
+
+/// How many seconds an issued access token remains valid for.
+const ACCESS_TOKEN_LIFETIME_SECONDS: u64 = 3600;
+
+/// The shape of a `POST /token` request body, documented for [`utoipa`]. Actual parsing happens in
+/// [`TokenRequest::from_body`], which additionally enforces the "empty value means unsent" and
+/// "repeated parameter is an error" rules that [`parse_authorization_query`](crate::auth)'s tests
+/// hold the authorization query to.
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct TokenRequestBody {
+    /// Must be `"authorization_code"`; this is the only grant type supported.
+    grant_type: String,
+    /// The authorization code previously issued by `/authorization`.
+    code: String,
+    /// Must match the `redirect_uri` the code was issued with, once both are parsed as URLs.
+    redirect_uri: String,
+    /// The `client_id` the code was issued to.
+    client_id: String,
+}
+
+/// A parsed and validated `POST /token` request for the `authorization_code` grant.
+#[derive(Debug, PartialEq, Eq)]
+struct TokenRequest {
+    code: String,
+    redirect_uri: String,
+    client_id: String,
+}
+
+#[derive(Debug, thiserror::Error, PartialEq)]
+#[error("token request parsing error")]
+pub enum TokenError {
+    #[error("the request body is not in urlencoded format")]
+    ParsingError,
+    #[error("repeated parameter")]
+    RepeatedParameter,
+    #[error("missing parameter {0:?}")]
+    MissingParameter(&'static str),
+    #[error("only the \"authorization_code\" grant_type is supported")]
+    UnsupportedGrantType,
+    #[error("the authorization code is invalid, expired, or already redeemed")]
+    InvalidGrant,
+    #[error("the client_id does not match the one the authorization code was issued to")]
+    ClientIdMismatch,
+    #[error("the redirect_uri does not match the one the authorization code was issued with")]
+    RedirectUriMismatch,
+}
+
+impl TokenError {
+    fn standard_error_text(&self) -> &'static str {
+        match self {
+            Self::ParsingError | Self::RepeatedParameter | Self::MissingParameter(_) => {
+                "invalid_request"
+            }
+            Self::UnsupportedGrantType => "unsupported_grant_type",
+            Self::InvalidGrant | Self::ClientIdMismatch | Self::RedirectUriMismatch => {
+                "invalid_grant"
+            }
+        }
+    }
+}
+
+/// The RFC 6749 section 5.2 JSON error body.
+#[derive(Debug, Serialize)]
+struct TokenErrorBody {
+    error: &'static str,
+    error_description: String,
+}
+
+impl IntoResponse for TokenError {
+    fn into_response(self) -> Response {
+        let body = TokenErrorBody {
+            error: self.standard_error_text(),
+            error_description: self.to_string(),
+        };
+
+        (StatusCode::BAD_REQUEST, Json(body)).into_response()
+    }
+}
+
+/// Implementations for parsing
+impl TokenRequest {
+    /// Tries to generate itself from a still percent-encoded request body.
+    fn from_body(body: &str) -> Result<Self, TokenError> {
+        use TokenParams as Params;
+        use TokenError as Error;
+
+        #[derive(Deserialize)]
+        struct DeserializableSelf {
+            grant_type: Option<String>,
+            code: Option<String>,
+            redirect_uri: Option<String>,
+            client_id: Option<String>,
+        }
+
+        impl DeserializableSelf {
+            fn empty_to_none(self) -> Self {
+                fn empty_to_none(option: Option<String>) -> Option<String> {
+                    option.and_then(|s| if s == "" { None } else { Some(s) })
+                }
+                DeserializableSelf {
+                    grant_type: empty_to_none(self.grant_type),
+                    code: empty_to_none(self.code),
+                    redirect_uri: empty_to_none(self.redirect_uri),
+                    client_id: empty_to_none(self.client_id),
+                }
+            }
+        }
+
+        let deserializable_self = match serde_urlencoded::from_str::<DeserializableSelf>(body) {
+            Ok(deserializable_self) => deserializable_self,
+            Err(error) => {
+                let error = if error.to_string().starts_with("duplicate field") {
+                    Error::RepeatedParameter
+                } else {
+                    Error::ParsingError
+                };
+                return Err(error);
+            }
+        }
+        .empty_to_none();
+
+        let grant_type = deserializable_self
+            .grant_type
+            .ok_or(Error::MissingParameter(Params::GrantType.name()))?;
+
+        if grant_type != SUPPORTED_GRANT_TYPE {
+            return Err(Error::UnsupportedGrantType);
+        }
+
+        let code = deserializable_self
+            .code
+            .ok_or(Error::MissingParameter(Params::Code.name()))?;
+
+        let redirect_uri = deserializable_self
+            .redirect_uri
+            .ok_or(Error::MissingParameter(Params::RedirectUri.name()))?;
+
+        let client_id = deserializable_self
+            .client_id
+            .ok_or(Error::MissingParameter(Params::ClientId.name()))?;
+
+        Ok(TokenRequest {
+            code,
+            redirect_uri,
+            client_id,
+        })
+    }
+
+    /// Simply maps to `Self::from_body`
+    #[instrument(name = "parse_token_request", skip_all)]
+    async fn internal_from_request(req: Request) -> Result<Self, TokenError> {
+        let body = to_bytes(req.into_body(), usize::MAX)
+            .await
+            .map_err(|_err| TokenError::ParsingError)?;
+        let body = std::str::from_utf8(&body).map_err(|_err| TokenError::ParsingError)?;
+
+        tracing::trace!("Started to parse token request body: {:?}", body);
+
+        let result = Self::from_body(body);
+
+        tracing::trace!("Resulted in: {:?}", result);
+
+        result
+    }
+
+    /// Redeems `self.code` against `code_store`, checking that `client_id` and `redirect_uri`
+    /// match what the code was issued with, per [RFC 6749 section
+    /// 4.1.3](https://datatracker.ietf.org/doc/html/rfc6749#section-4.1.3).
+    fn redeem(self, code_store: &dyn CodeStore) -> Result<TokenResponse, TokenError> {
+        let IssuedAuthorization {
+            client_id,
+            redirect_uri,
+            scope,
+            // TODO: Embed this in the ID token once `/token` can issue one for the `id_token`
+            // response type.
+            nonce: _nonce,
+        } = code_store.consume(&self.code).ok_or(TokenError::InvalidGrant)?;
+
+        if client_id != self.client_id {
+            return Err(TokenError::ClientIdMismatch);
+        }
+
+        if !redirect_uri.matches_raw(&self.redirect_uri) {
+            return Err(TokenError::RedirectUriMismatch);
+        }
+
+        Ok(TokenResponse::issue(&scope))
+    }
+}
+
+#[async_trait::async_trait]
+impl<S> FromRequest<S> for TokenRequest
+where
+    S: Send + Sync,
+{
+    type Rejection = TokenError;
+    /// Simply maps to `Self::internal_from_request`
+    async fn from_request(req: Request, _state: &S) -> Result<Self, Self::Rejection> {
+        Self::internal_from_request(req).await
+    }
+}
+
+/// The standard OAuth 2.0 access token response, as defined by [RFC 6749 section
+/// 5.1](https://datatracker.ietf.org/doc/html/rfc6749#section-5.1).
+#[derive(Debug, Serialize, PartialEq, utoipa::ToSchema)]
+pub struct TokenResponse {
+    access_token: String,
+    token_type: &'static str,
+    expires_in: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scope: Option<String>,
+}
+
+impl TokenResponse {
+    /// Issues a fresh access token for `scope`. `scope` is only included in the response when it
+    /// is non-empty, per RFC 6749 section 5.1: its absence means "the scope originally granted".
+    fn issue(scope: &ScopeList) -> Self {
+        let mut access_token_bytes = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut access_token_bytes);
+
+        Self {
+            access_token: URL_SAFE_NO_PAD.encode(access_token_bytes),
+            token_type: "bearer",
+            expires_in: ACCESS_TOKEN_LIFETIME_SECONDS,
+            scope: (!scope.0.is_empty())
+                .then(|| scope.0.iter().map(Scope::as_str).collect::<Vec<_>>().join(" ")),
+        }
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/token",
+    request_body(
+        content = TokenRequestBody,
+        content_type = "application/x-www-form-urlencoded",
+        description = "An authorization_code grant, as defined by RFC 6749 section 4.1.3."
+    ),
+    responses(
+        (status = 200, description = "The authorization code was valid; an access token was issued.", body = TokenResponse),
+        (status = 400, description = "grant_type is missing or unsupported, a required parameter is missing or repeated, or the code/client_id/redirect_uri did not match what was issued, per RFC 6749 section 5.2.")
+    )
+)]
+pub async fn token(request: TokenRequest) -> Result<Json<TokenResponse>, TokenError> {
+    // TODO: Source the CodeStore from server state once `/authorization` issues and stores codes
+    // instead of returning a placeholder response.
+    request.redeem(&NoIssuedCodes).map(Json)
+}
@@ -0,0 +1,252 @@
+use super::code_store::{CodeStore, IssuedAuthorization};
+use super::{TokenError as Error, TokenParams as Params, TokenRequest};
+use crate::scope::ScopeList;
+
+const VALID_GRANT_TYPE_PARAM: (&str, &str) = ("grant_type", "authorization_code");
+const VALID_CODE_PARAM: (&str, &str) = ("code", "valid_code");
+const VALID_REDIRECT_URI_PARAM: (&str, &str) =
+    ("redirect_uri", "https://example.org/foo/bar?hey=now");
+const VALID_CLIENT_ID_PARAM: (&str, &str) = ("client_id", "valid_client_id");
+
+fn valid_redirect_uri() -> crate::auth::RedirectUri {
+    crate::auth::RedirectUri::new(
+        url::Url::parse(VALID_REDIRECT_URI_PARAM.1).unwrap(),
+        VALID_CLIENT_ID_PARAM.1,
+        &crate::auth::AllowAllRedirectUris,
+    )
+    .unwrap()
+}
+
+fn valid_token_request() -> TokenRequest {
+    TokenRequest {
+        code: VALID_CODE_PARAM.1.to_owned(),
+        redirect_uri: VALID_REDIRECT_URI_PARAM.1.to_owned(),
+        client_id: VALID_CLIENT_ID_PARAM.1.to_owned(),
+    }
+}
+
+/// A [`CodeStore`] that hands back a fixed [`IssuedAuthorization`] for one specific code, and
+/// `None` for anything else.
+struct FixedCodeStore {
+    code: &'static str,
+    client_id: &'static str,
+    redirect_uri: crate::auth::RedirectUri,
+    scope: &'static str,
+}
+
+impl CodeStore for FixedCodeStore {
+    fn consume(&self, code: &str) -> Option<IssuedAuthorization> {
+        if code != self.code {
+            return None;
+        }
+
+        Some(IssuedAuthorization {
+            client_id: self.client_id.to_owned(),
+            redirect_uri: self.redirect_uri.clone(),
+            // `ScopeList::try_from("")` is an error, so an empty grant is built directly.
+            scope: if self.scope.is_empty() {
+                ScopeList(Vec::new())
+            } else {
+                ScopeList::try_from(self.scope).unwrap()
+            },
+            nonce: None,
+        })
+    }
+}
+
+#[test]
+fn trivial_body() {
+    let body = serde_urlencoded::to_string([
+        VALID_GRANT_TYPE_PARAM,
+        VALID_CODE_PARAM,
+        VALID_REDIRECT_URI_PARAM,
+        VALID_CLIENT_ID_PARAM,
+    ])
+    .unwrap();
+
+    assert_eq!(TokenRequest::from_body(&body), Ok(valid_token_request()));
+}
+
+#[test]
+fn missing_parameters() {
+    let missing_grant_type =
+        serde_urlencoded::to_string([VALID_CODE_PARAM, VALID_REDIRECT_URI_PARAM, VALID_CLIENT_ID_PARAM])
+            .unwrap();
+
+    assert_eq!(
+        TokenRequest::from_body(&missing_grant_type),
+        Err(Error::MissingParameter(Params::GrantType.name()))
+    );
+
+    // parameters without values must be treated as unsent, as per section 3.1 of RFC 6749
+    let empty_grant_type = serde_urlencoded::to_string([
+        ("grant_type", ""),
+        VALID_CODE_PARAM,
+        VALID_REDIRECT_URI_PARAM,
+        VALID_CLIENT_ID_PARAM,
+    ])
+    .unwrap();
+
+    assert_eq!(
+        TokenRequest::from_body(&empty_grant_type),
+        Err(Error::MissingParameter(Params::GrantType.name()))
+    );
+
+    let missing_code = serde_urlencoded::to_string([
+        VALID_GRANT_TYPE_PARAM,
+        VALID_REDIRECT_URI_PARAM,
+        VALID_CLIENT_ID_PARAM,
+    ])
+    .unwrap();
+
+    assert_eq!(
+        TokenRequest::from_body(&missing_code),
+        Err(Error::MissingParameter(Params::Code.name()))
+    );
+
+    let missing_redirect_uri =
+        serde_urlencoded::to_string([VALID_GRANT_TYPE_PARAM, VALID_CODE_PARAM, VALID_CLIENT_ID_PARAM])
+            .unwrap();
+
+    assert_eq!(
+        TokenRequest::from_body(&missing_redirect_uri),
+        Err(Error::MissingParameter(Params::RedirectUri.name()))
+    );
+
+    let missing_client_id =
+        serde_urlencoded::to_string([VALID_GRANT_TYPE_PARAM, VALID_CODE_PARAM, VALID_REDIRECT_URI_PARAM])
+            .unwrap();
+
+    assert_eq!(
+        TokenRequest::from_body(&missing_client_id),
+        Err(Error::MissingParameter(Params::ClientId.name()))
+    );
+}
+
+#[test]
+fn repeated_parameters() {
+    let repeated_grant_type = serde_urlencoded::to_string([
+        VALID_GRANT_TYPE_PARAM,
+        ("grant_type", ""),
+        VALID_CODE_PARAM,
+        VALID_REDIRECT_URI_PARAM,
+        VALID_CLIENT_ID_PARAM,
+    ])
+    .unwrap();
+
+    assert_eq!(
+        TokenRequest::from_body(&repeated_grant_type),
+        Err(Error::RepeatedParameter)
+    );
+
+    let repeated_code = serde_urlencoded::to_string([
+        VALID_GRANT_TYPE_PARAM,
+        VALID_CODE_PARAM,
+        ("code", ""),
+        VALID_REDIRECT_URI_PARAM,
+        VALID_CLIENT_ID_PARAM,
+    ])
+    .unwrap();
+
+    assert_eq!(
+        TokenRequest::from_body(&repeated_code),
+        Err(Error::RepeatedParameter)
+    );
+}
+
+#[test]
+fn unsupported_grant_type() {
+    let body = serde_urlencoded::to_string([
+        ("grant_type", "client_credentials"),
+        VALID_CODE_PARAM,
+        VALID_REDIRECT_URI_PARAM,
+        VALID_CLIENT_ID_PARAM,
+    ])
+    .unwrap();
+
+    assert_eq!(
+        TokenRequest::from_body(&body),
+        Err(Error::UnsupportedGrantType)
+    );
+}
+
+#[test]
+fn successful_redeem_includes_scope() {
+    let store = FixedCodeStore {
+        code: VALID_CODE_PARAM.1,
+        client_id: VALID_CLIENT_ID_PARAM.1,
+        redirect_uri: valid_redirect_uri(),
+        scope: "scope_a scope_b",
+    };
+
+    let response = valid_token_request().redeem(&store).unwrap();
+
+    assert_eq!(response.token_type, "bearer");
+    assert_eq!(response.expires_in, super::ACCESS_TOKEN_LIFETIME_SECONDS);
+    assert_eq!(response.scope, Some("scope_a scope_b".to_owned()));
+    assert!(!response.access_token.is_empty());
+}
+
+#[test]
+fn successful_redeem_omits_empty_scope() {
+    let store = FixedCodeStore {
+        code: VALID_CODE_PARAM.1,
+        client_id: VALID_CLIENT_ID_PARAM.1,
+        redirect_uri: valid_redirect_uri(),
+        scope: "",
+    };
+
+    let response = valid_token_request().redeem(&store).unwrap();
+
+    assert_eq!(response.scope, None);
+}
+
+#[test]
+fn unknown_code_is_invalid_grant() {
+    let store = FixedCodeStore {
+        code: "other_code",
+        client_id: VALID_CLIENT_ID_PARAM.1,
+        redirect_uri: valid_redirect_uri(),
+        scope: "",
+    };
+
+    assert_eq!(
+        valid_token_request().redeem(&store),
+        Err(Error::InvalidGrant)
+    );
+}
+
+#[test]
+fn client_id_mismatch() {
+    let store = FixedCodeStore {
+        code: VALID_CODE_PARAM.1,
+        client_id: "other_client_id",
+        redirect_uri: valid_redirect_uri(),
+        scope: "",
+    };
+
+    assert_eq!(
+        valid_token_request().redeem(&store),
+        Err(Error::ClientIdMismatch)
+    );
+}
+
+#[test]
+fn redirect_uri_mismatch() {
+    let store = FixedCodeStore {
+        code: VALID_CODE_PARAM.1,
+        client_id: VALID_CLIENT_ID_PARAM.1,
+        redirect_uri: crate::auth::RedirectUri::new(
+            url::Url::parse("https://example.org/other").unwrap(),
+            VALID_CLIENT_ID_PARAM.1,
+            &crate::auth::AllowAllRedirectUris,
+        )
+        .unwrap(),
+        scope: "",
+    };
+
+    assert_eq!(
+        valid_token_request().redeem(&store),
+        Err(Error::RedirectUriMismatch)
+    );
+}